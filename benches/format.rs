@@ -0,0 +1,68 @@
+#![feature(test)]
+
+extern crate test;
+
+use roc_dec::RocDec;
+use test::{black_box, Bencher};
+
+static SHORT: &str = "1.2";
+static LONG: &str = "170141183460469231731.687303715884105727";
+
+#[bench]
+fn dec_to_string_short(bench: &mut Bencher) {
+    let dec: RocDec = SHORT.parse().unwrap();
+
+    bench.iter(|| {
+        black_box(black_box(dec).to_string());
+    });
+}
+
+#[bench]
+fn dec_to_string_long(bench: &mut Bencher) {
+    let dec: RocDec = LONG.parse().unwrap();
+
+    bench.iter(|| {
+        black_box(black_box(dec).to_string());
+    });
+}
+
+#[bench]
+fn dec_to_str_buf_short(bench: &mut Bencher) {
+    let dec: RocDec = SHORT.parse().unwrap();
+    let mut buf = [0; RocDec::MAX_STR_LEN];
+
+    bench.iter(|| {
+        black_box(black_box(dec).to_str_buf(&mut buf).len());
+    });
+}
+
+#[bench]
+fn dec_to_str_buf_long(bench: &mut Bencher) {
+    let dec: RocDec = LONG.parse().unwrap();
+    let mut buf = [0; RocDec::MAX_STR_LEN];
+
+    bench.iter(|| {
+        black_box(black_box(dec).to_str_buf(&mut buf).len());
+    });
+}
+
+#[bench]
+fn i128_to_string_long(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(black_box(i128::MAX).to_string());
+    });
+}
+
+#[bench]
+fn f64_to_string_short(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(black_box(1.2f64).to_string());
+    });
+}
+
+#[bench]
+fn f64_to_string_long(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(black_box(170141183460469231731.687303715884105727f64).to_string());
+    });
+}
@@ -0,0 +1,110 @@
+#![feature(test)]
+
+extern crate test;
+
+use decimal::d128;
+use roc_dec::RocDec;
+use std::str::FromStr;
+use test::{black_box, Bencher};
+
+static SHORT: &str = "1.2";
+static LONG: &str = "170141183460469231731.687303715884105727";
+
+#[bench]
+fn dec_parse_short(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(black_box(SHORT).parse::<RocDec>().unwrap());
+    });
+}
+
+#[bench]
+fn dec_parse_long(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(black_box(LONG).parse::<RocDec>().unwrap());
+    });
+}
+
+#[bench]
+fn dec_parse_bytes_short(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(RocDec::parse_bytes(black_box(SHORT.as_bytes())).unwrap());
+    });
+}
+
+#[bench]
+fn dec_parse_bytes_long(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(RocDec::parse_bytes(black_box(LONG.as_bytes())).unwrap());
+    });
+}
+
+#[bench]
+fn i128_split_parse_short(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(split_parse(black_box(SHORT)).unwrap());
+    });
+}
+
+#[bench]
+fn i128_split_parse_long(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(split_parse(black_box(LONG)).unwrap());
+    });
+}
+
+#[bench]
+fn f64_parse_short(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(black_box(SHORT).parse::<f64>().unwrap());
+    });
+}
+
+#[bench]
+fn f64_parse_long(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(black_box(LONG).parse::<f64>().unwrap());
+    });
+}
+
+#[bench]
+fn d128_parse_short(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(d128::from_str(black_box(SHORT)).unwrap());
+    });
+}
+
+#[bench]
+fn d128_parse_long(bench: &mut Bencher) {
+    bench.iter(|| {
+        black_box(d128::from_str(black_box(LONG)).unwrap());
+    });
+}
+
+/// How RocDec used to parse strings, before it had a digit scanner: split
+/// on the "." and run i128::parse on each side. This is here as a baseline
+/// for the dec_parse benchmarks.
+fn split_parse(value: &str) -> Option<i128> {
+    const DECIMAL_PLACES: u32 = 18;
+
+    let mut parts = value.split('.');
+    let before_point = parts.next()?;
+    let after_point = parts.next()?;
+
+    if parts.next().is_some() || after_point.len() > DECIMAL_PLACES as usize {
+        return None;
+    }
+
+    let trailing_zeroes = DECIMAL_PLACES - after_point.len() as u32;
+    let lo = after_point.parse::<i128>().ok()? * 10i128.pow(trailing_zeroes);
+    let lo = if before_point.starts_with('-') {
+        -lo
+    } else {
+        lo
+    };
+
+    before_point
+        .parse::<i128>()
+        .ok()?
+        .checked_mul(10i128.pow(DECIMAL_PLACES))?
+        .checked_add(lo)
+}
@@ -23,7 +23,13 @@ fuzz_target!(|data: &[u8]| {
 
     dbg!(num);
 
-    let dec_to_str = roc_dec::fuzz_new(num).to_string();
+    let dec = RocDec::from_raw(num);
+
+    assert_eq!(num, dec.to_raw());
+    assert_eq!(dec, RocDec::from_le_bytes(dec.to_le_bytes()));
+    assert_eq!(dec, RocDec::from_be_bytes(dec.to_be_bytes()));
+
+    let dec_to_str = dec.to_string();
 
     // There should be a dot with something before it and something after.
     let mut dec_pieces = dec_to_str.split('.');
@@ -39,6 +45,11 @@ fuzz_target!(|data: &[u8]| {
     // We shouldn't have more than the maximum number of before-dot digits.
     assert!(before_dot.len() <= 39 - RocDec::DECIMAL_PLACES as usize);
 
+    // The chunked formatter should agree with the digit-at-a-time one it
+    // replaced, both through to_string and through the stack buffer.
+    assert_eq!(old_to_string(num), dec_to_str);
+    assert_eq!(dec_to_str, dec.to_str_buf(&mut [0; RocDec::MAX_STR_LEN]));
+
     // if num == 0 {
     //     // 0 is a special case; it should print 0.0
     //     assert_eq!("0.0", dec_to_str);
@@ -48,3 +59,60 @@ fuzz_target!(|data: &[u8]| {
     //     assert_eq!(num.to_string(), format!("{}{}", before_dot, after_dot));
     // }
 });
+
+/// How RocDec used to format itself, before it split off the fractional part
+/// and wrote digits in pairs: one digit at a time from the end, then reverse.
+fn old_to_string(num: i128) -> String {
+    const DECIMAL_PLACES: u32 = RocDec::DECIMAL_PLACES;
+
+    let is_negative = num.is_negative();
+
+    let mut num_u128 = match num.checked_abs() {
+        Some(0) => return "0.0".to_string(),
+        Some(answer) => answer as u128,
+        None => return "-170141183460469231731.687303715884105728".to_string(),
+    };
+
+    let mut bytes = Vec::with_capacity(RocDec::MAX_STR_LEN);
+    let mut display_zeroes = false;
+    let mut decimal_places_used = 0;
+
+    while num_u128 != 0 {
+        let ascii_byte = (num_u128 % 10) as u8 + b'0';
+
+        if display_zeroes || ascii_byte != b'0' {
+            bytes.push(ascii_byte);
+            display_zeroes = true;
+        }
+
+        num_u128 /= 10;
+        decimal_places_used += 1;
+
+        if decimal_places_used == DECIMAL_PLACES {
+            if num_u128 == 0 {
+                bytes.extend_from_slice(b".0");
+                break;
+            }
+
+            if !display_zeroes {
+                bytes.push(b'0');
+            }
+
+            bytes.push(b'.');
+            display_zeroes = true;
+        }
+    }
+
+    if decimal_places_used < DECIMAL_PLACES {
+        bytes.extend(std::iter::repeat(b'0').take((DECIMAL_PLACES - decimal_places_used) as usize));
+        bytes.extend_from_slice(b".0");
+    }
+
+    if is_negative {
+        bytes.push(b'-');
+    }
+
+    bytes.reverse();
+
+    String::from_utf8(bytes).unwrap()
+}
@@ -0,0 +1,266 @@
+use crate::{div_u256_by_u128, mul_i128, RocDec, U256};
+
+/// A running total of RocDecs which can't overflow partway through a sum.
+///
+/// Adding up many large values can temporarily exceed RocDec::MAX even when
+/// the final answer fits, e.g. long and short positions which mostly cancel
+/// out. This keeps the total in 256 bits, and only checks whether it fits
+/// in a RocDec at the very end.
+///
+/// The total is also kept with 2 * DECIMAL_PLACES decimal places, which
+/// means `add_product` can add the exact product of two RocDecs without
+/// truncating it first.
+///
+/// If the total ever overflows 256 bits, which takes adding up products of
+/// values near RocDec::MIN or RocDec::MAX, the accumulator remembers which
+/// direction it overflowed in and stays that way: `finish` and `checked_div`
+/// return None, and `finish_saturating` clamps.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct RocDecAccumulator {
+    // Two's complement, scaled by 10^(2 * DECIMAL_PLACES).
+    total: U256,
+    // Some(is_negative) once the total has overflowed, in that direction.
+    overflow: Option<bool>,
+}
+
+impl Default for RocDecAccumulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RocDecAccumulator {
+    /// An accumulator whose total starts at zero.
+    pub fn new() -> Self {
+        RocDecAccumulator {
+            total: U256::ZERO,
+            overflow: None,
+        }
+    }
+
+    /// Add a value to the running total.
+    ///
+    /// Each value is at most 2^187 once scaled, so overflowing the total
+    /// would take more than 2^68 calls to this.
+    #[inline(always)]
+    pub fn add(&mut self, dec: RocDec) {
+        self.add_scaled(mul_i128(dec.0, RocDec::ONE_POINT_ZERO));
+    }
+
+    /// Subtract a value from the running total.
+    #[inline(always)]
+    pub fn sub(&mut self, dec: RocDec) {
+        self.add_scaled(mul_i128(dec.0, -RocDec::ONE_POINT_ZERO));
+    }
+
+    /// Add the exact product of two values to the running total.
+    ///
+    /// Unlike `a * b`, this keeps every decimal place of the product,
+    /// so rounding only happens once, in `finish`.
+    ///
+    /// A single product can be as large as 2^254, so summing products of
+    /// values near RocDec::MIN or RocDec::MAX can overflow the total, which
+    /// makes `finish` return None from then on.
+    #[inline(always)]
+    pub fn add_product(&mut self, a: RocDec, b: RocDec) {
+        self.add_scaled(mul_i128(a.0, b.0));
+    }
+
+    /// The total as a RocDec, or None if it doesn't fit in one.
+    ///
+    /// Any extra decimal places left over from `add_product` are truncated
+    /// toward zero, the same way RocDec's own multiplication does.
+    pub fn finish(&self) -> Option<RocDec> {
+        self.checked_div(RocDec(RocDec::ONE_POINT_ZERO))
+    }
+
+    /// The total as a RocDec, clamped to RocDec::MIN or RocDec::MAX if it
+    /// doesn't fit in one.
+    pub fn finish_saturating(&self) -> RocDec {
+        match (self.finish(), self.overflow) {
+            (Some(answer), _) => answer,
+            (None, Some(true)) => RocDec::MIN,
+            (None, Some(false)) => RocDec::MAX,
+            (None, None) if self.total.is_negative() => RocDec::MIN,
+            (None, None) => RocDec::MAX,
+        }
+    }
+
+    /// The total divided by `divisor`, or None if the answer doesn't fit in
+    /// a RocDec, `divisor` is zero, or the total has overflowed.
+    ///
+    /// This truncates toward zero, the same way RocDec's own division does.
+    ///
+    /// Dividing before converting back to a RocDec means this works even
+    /// when the total itself wouldn't fit in one, e.g. for averages.
+    pub fn checked_div(&self, divisor: RocDec) -> Option<RocDec> {
+        if divisor.0 == 0 || self.overflow.is_some() {
+            return None;
+        }
+
+        // The total has 2 * DECIMAL_PLACES decimal places, and the divisor
+        // has DECIMAL_PLACES, so the quotient has exactly DECIMAL_PLACES.
+        let (magnitude, is_negative) = self.total.unsigned_abs();
+        let quotient = div_u256_by_u128(magnitude, divisor.0.unsigned_abs());
+
+        if is_negative != divisor.0.is_negative() {
+            quotient.wrapping_neg().checked_to_i128().map(RocDec)
+        } else {
+            quotient.checked_to_i128().map(RocDec)
+        }
+    }
+
+    #[inline(always)]
+    fn add_scaled(&mut self, scaled: U256) {
+        if self.overflow.is_some() {
+            return;
+        }
+
+        match self.total.checked_add_signed(scaled) {
+            Some(answer) => self.total = answer,
+            // Signed addition can only overflow when both operands have
+            // the same sign, so that's the direction it overflowed in.
+            None => self.overflow = Some(scaled.is_negative()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RocDec, RocDecAccumulator};
+    use std::convert::TryInto;
+
+    fn dec(string: &str) -> RocDec {
+        string.try_into().unwrap()
+    }
+
+    #[test]
+    fn empty() {
+        let acc = RocDecAccumulator::new();
+
+        assert_eq!(Some(RocDec(0)), acc.finish());
+        assert_eq!(RocDec(0), acc.finish_saturating());
+    }
+
+    #[test]
+    fn add_and_sub() {
+        let mut acc = RocDecAccumulator::new();
+
+        acc.add(dec("1.5"));
+        acc.add(dec("-0.25"));
+        acc.sub(dec("3.0"));
+
+        assert_eq!(Some(dec("-1.75")), acc.finish());
+    }
+
+    #[test]
+    fn exceeds_max_temporarily() {
+        let mut acc = RocDecAccumulator::new();
+
+        acc.add(RocDec::MAX);
+        acc.add(RocDec::MAX);
+        acc.add(RocDec::MAX);
+
+        assert_eq!(None, acc.finish());
+        assert_eq!(RocDec::MAX, acc.finish_saturating());
+
+        acc.sub(RocDec::MAX);
+        acc.sub(RocDec::MAX);
+
+        assert_eq!(Some(RocDec::MAX), acc.finish());
+    }
+
+    #[test]
+    fn exceeds_min_temporarily() {
+        let mut acc = RocDecAccumulator::new();
+
+        acc.add(RocDec::MIN);
+        acc.add(RocDec::MIN);
+
+        assert_eq!(None, acc.finish());
+        assert_eq!(RocDec::MIN, acc.finish_saturating());
+
+        acc.sub(RocDec::MIN);
+
+        assert_eq!(Some(RocDec::MIN), acc.finish());
+    }
+
+    #[test]
+    fn add_product_keeps_extra_digits() {
+        let mut acc = RocDecAccumulator::new();
+
+        // Each product on its own would be truncated to 0.0 by `*`, but
+        // together they add up to a representable value.
+        for _ in 0..10 {
+            acc.add_product(dec("0.000000001"), dec("0.0000000001"));
+        }
+
+        assert_eq!(Some(RocDec(1)), acc.finish());
+    }
+
+    #[test]
+    fn add_product_signs() {
+        let mut acc = RocDecAccumulator::new();
+
+        acc.add_product(dec("-2.0"), dec("3.5"));
+        acc.add_product(dec("-1.5"), dec("-2.0"));
+
+        assert_eq!(Some(dec("-4.0")), acc.finish());
+    }
+
+    #[test]
+    fn finish_truncates_toward_zero() {
+        let mut acc = RocDecAccumulator::new();
+
+        acc.add_product(dec("0.000000000000000001"), dec("0.9"));
+        assert_eq!(Some(RocDec(0)), acc.finish());
+
+        acc.add_product(dec("-0.000000000000000002"), dec("0.9"));
+        assert_eq!(Some(RocDec(0)), acc.finish());
+    }
+
+    #[test]
+    fn checked_div() {
+        let mut acc = RocDecAccumulator::new();
+
+        acc.add(RocDec::MAX);
+        acc.add(RocDec::MAX);
+
+        assert_eq!(Some(RocDec::MAX), acc.checked_div(dec("2.0")));
+        assert_eq!(Some(-RocDec::MAX), acc.checked_div(dec("-2.0")));
+        assert_eq!(None, acc.checked_div(RocDec(0)));
+    }
+
+    #[test]
+    fn overflow_is_sticky() {
+        let mut acc = RocDecAccumulator::new();
+
+        // Each of these is 2^254, so the second one overflows 256 bits.
+        acc.add_product(RocDec::MIN, RocDec::MIN);
+        acc.add_product(RocDec::MIN, RocDec::MIN);
+
+        assert_eq!(None, acc.finish());
+        assert_eq!(None, acc.checked_div(RocDec::MAX));
+        assert_eq!(RocDec::MAX, acc.finish_saturating());
+
+        // Subtracting afterwards doesn't bring it back.
+        acc.add_product(RocDec::MIN, RocDec::MAX);
+        acc.add_product(RocDec::MIN, RocDec::MAX);
+        acc.add_product(RocDec::MIN, RocDec::MAX);
+
+        assert_eq!(None, acc.finish());
+        assert_eq!(RocDec::MAX, acc.finish_saturating());
+    }
+
+    #[test]
+    fn overflow_negative() {
+        let mut acc = RocDecAccumulator::new();
+
+        for _ in 0..3 {
+            acc.add_product(RocDec::MIN, RocDec::MAX);
+        }
+
+        assert_eq!(None, acc.finish());
+        assert_eq!(RocDec::MIN, acc.finish_saturating());
+    }
+}
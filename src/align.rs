@@ -0,0 +1,209 @@
+use crate::{FormatOptions, RocDec, RoundingMode};
+
+/// Options for `align_decimals`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct AlignOptions {
+    min_places: u32,
+    format: FormatOptions,
+}
+
+impl Default for AlignOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AlignOptions {
+    pub fn new() -> Self {
+        AlignOptions {
+            min_places: 0,
+            format: FormatOptions::new(),
+        }
+    }
+
+    /// Show at least this many digits after the decimal point, filling in
+    /// zeroes as needed, e.g. "1.50" rather than "1.5" for 2. Values with
+    /// more decimal places than this keep all of them.
+    pub fn min_places(mut self, places: u32) -> Self {
+        self.min_places = places;
+
+        self
+    }
+
+    /// Format each value with these options, e.g. to group digits. If they
+    /// have fixed decimal places, those take precedence over `min_places`.
+    pub fn format_options(mut self, format: FormatOptions) -> Self {
+        self.format = format;
+
+        self
+    }
+}
+
+/// Format a column of values so that their decimal points line up.
+///
+/// Every string in the result has the same width (in chars): shorter
+/// integer parts are padded with spaces on the left, and shorter fractional
+/// parts are padded with spaces on the right. Anything after the digits,
+/// such as the ")" of a negative number in parentheses, counts as part of
+/// the fractional side.
+pub fn align_decimals(values: &[RocDec], options: &AlignOptions) -> Vec<String> {
+    let strings: Vec<String> = values
+        .iter()
+        .map(|value| {
+            let format = match options.format.decimal_places {
+                Some(_) => options.format,
+                // Formatting to at least as many places as the value already
+                // has can't round, so the mode doesn't matter.
+                None => options.format.decimal_places(
+                    frac_digit_count(value).max(options.min_places),
+                    RoundingMode::Down,
+                ),
+            };
+
+            value.to_string_with(&format)
+        })
+        .collect();
+
+    // How many chars go before and after the alignment point in each string.
+    let widths: Vec<(usize, usize)> = strings
+        .iter()
+        .map(|string| {
+            let total = string.chars().count();
+            let left = alignment_point(string, options.format.decimal_separator);
+
+            (left, total - left)
+        })
+        .collect();
+
+    let left_width = widths.iter().map(|(left, _)| *left).max().unwrap_or(0);
+    let right_width = widths.iter().map(|(_, right)| *right).max().unwrap_or(0);
+
+    strings
+        .iter()
+        .zip(widths)
+        .map(|(string, (left, right))| {
+            let mut aligned = String::with_capacity(string.len() + left_width + right_width);
+
+            aligned.extend(std::iter::repeat(' ').take(left_width - left));
+            aligned.push_str(string);
+            aligned.extend(std::iter::repeat(' ').take(right_width - right));
+
+            aligned
+        })
+        .collect()
+}
+
+/// How many digits `to_string` shows after the decimal point, which is
+/// always at least 1.
+fn frac_digit_count(value: &RocDec) -> u32 {
+    let mut buf = [0; RocDec::MAX_STR_LEN];
+    let string = value.to_str_buf(&mut buf);
+
+    string.find('.').map_or(0, |point| string.len() - point - 1) as u32
+}
+
+/// The number of chars before the decimal separator, or before whatever
+/// follows the last digit if there's no separator (e.g. with 0 places).
+fn alignment_point(string: &str, decimal_separator: char) -> usize {
+    let mut last_digit_end = 0;
+
+    for (index, c) in string.chars().enumerate() {
+        if c == decimal_separator {
+            return index;
+        }
+
+        // This includes non-ASCII digits, e.g. from DigitSet::Devanagari.
+        if c.is_numeric() {
+            last_digit_end = index + 1;
+        }
+    }
+
+    last_digit_end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{align_decimals, AlignOptions};
+    use crate::{FormatOptions, Locale, NegativeStyle, RocDec, RoundingMode};
+    use std::convert::TryInto;
+
+    fn assert_aligned(strings: &[&str], options: &AlignOptions, expected: &[&str]) {
+        let values: Vec<RocDec> = strings
+            .iter()
+            .map(|string| (*string).try_into().unwrap())
+            .collect();
+
+        assert_eq!(expected.to_vec(), align_decimals(&values, options));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(align_decimals(&[], &AlignOptions::new()).is_empty());
+    }
+
+    #[test]
+    fn defaults() {
+        assert_aligned(
+            &["1.5", "-10.25", "100.0", "0.125"],
+            &AlignOptions::new(),
+            &["  1.5  ", "-10.25 ", "100.0  ", "  0.125"],
+        );
+    }
+
+    #[test]
+    fn min_places() {
+        assert_aligned(
+            &["1.5", "-10.25", "100.0", "0.125"],
+            &AlignOptions::new().min_places(2),
+            &["  1.50 ", "-10.25 ", "100.00 ", "  0.125"],
+        );
+    }
+
+    #[test]
+    fn fixed_places() {
+        let format = FormatOptions::new()
+            .group_separator(',')
+            .decimal_places(2, RoundingMode::HalfUp);
+
+        assert_aligned(
+            &["1234.5", "7.125", "-1000000.0"],
+            &AlignOptions::new().format_options(format).min_places(4),
+            &["     1,234.50", "         7.13", "-1,000,000.00"],
+        );
+    }
+
+    #[test]
+    fn no_decimal_point() {
+        let format = FormatOptions::new()
+            .decimal_places(0, RoundingMode::HalfEven)
+            .negative_style(NegativeStyle::Parentheses);
+
+        assert_aligned(
+            &["5.0", "-120.0", "33.0"],
+            &AlignOptions::new().format_options(format),
+            &["   5 ", "(120)", "  33 "],
+        );
+    }
+
+    #[test]
+    fn parentheses() {
+        let format = FormatOptions::new().negative_style(NegativeStyle::Parentheses);
+
+        assert_aligned(
+            &["12.5", "-3.25"],
+            &AlignOptions::new().format_options(format).min_places(2),
+            &["12.50 ", "(3.25)"],
+        );
+    }
+
+    #[test]
+    fn locale() {
+        assert_aligned(
+            &["1234.5", "0.75"],
+            &AlignOptions::new()
+                .format_options(FormatOptions::for_locale(Locale::DeDe))
+                .min_places(2),
+            &["1.234,50", "    0,75"],
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{RocDec, RocDecAccumulator, RoundingMode};
+
+/// One period of a loan's amortization schedule.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct AmortizationRow {
+    /// Which period this is, starting from 1.
+    pub period: u32,
+    /// The total amount paid this period (interest + principal).
+    pub payment: RocDec,
+    /// The part of the payment which went toward interest.
+    pub interest: RocDec,
+    /// The part of the payment which went toward paying down the balance.
+    pub principal: RocDec,
+    /// The balance still owed after this period's payment.
+    pub balance: RocDec,
+}
+
+/// The fixed payment which pays off `principal` over `periods` periods at
+/// `rate` interest per period, rounded to `places` decimal places.
+///
+/// This uses the standard annuity formula:
+///
+/// ```text
+/// payment = principal * rate * (1 + rate)^periods / ((1 + rate)^periods - 1)
+/// ```
+///
+/// ...or simply principal / periods if the rate is zero.
+///
+/// Returns None if the payment or any intermediate value doesn't fit in a
+/// RocDec, e.g. for a very long term, or if (1 + rate)^periods is exactly 1,
+/// which makes the formula divide by zero.
+pub fn payment(
+    principal: RocDec,
+    rate: RocDec,
+    periods: u32,
+    places: u32,
+    mode: RoundingMode,
+) -> Option<RocDec> {
+    if periods == 0 {
+        return Some(RocDec(0));
+    }
+
+    let one = RocDec(RocDec::ONE_POINT_ZERO);
+
+    let unrounded = if rate.0 == 0 {
+        checked_div(principal, RocDec::from(periods))?
+    } else {
+        let growth = pow(checked_add(one, rate)?, periods)?;
+
+        // Dividing growth by (growth - 1) first keeps the intermediate values
+        // close to 1, so a large principal is less likely to overflow.
+        let factor = checked_div(growth, checked_sub(growth, one)?)?;
+
+        checked_mul(checked_mul(principal, rate)?, factor)?
+    };
+
+    unrounded.checked_round(places, mode)
+}
+
+/// Build the full amortization schedule for a loan.
+///
+/// Interest for each period is rounded to `places` decimal places using
+/// `mode`, and the rest of the payment goes toward the principal. Since the
+/// payment itself is rounded, the final period's payment is adjusted so that
+/// it pays off exactly the remaining balance, leaving a balance of zero.
+///
+/// No payment is ever more than the interest plus the remaining balance, so
+/// the balance never goes negative. If rounding the payment up pays off the
+/// loan early, e.g. for a tiny principal, the schedule stops there and has
+/// fewer than `periods` rows.
+///
+/// The principal is expected to already be rounded to `places` decimal places;
+/// if it isn't, the final payment will carry the extra digits.
+///
+/// Returns None if `payment` would, or if any period's interest or balance
+/// doesn't fit in a RocDec.
+pub fn schedule(
+    principal: RocDec,
+    rate: RocDec,
+    periods: u32,
+    places: u32,
+    mode: RoundingMode,
+) -> Option<Vec<AmortizationRow>> {
+    let regular_payment = payment(principal, rate, periods, places, mode)?;
+    let mut rows = Vec::with_capacity(periods as usize);
+    let mut balance = principal;
+
+    for period in 1..=periods {
+        let interest = checked_mul(balance, rate)?.checked_round(places, mode)?;
+
+        let payoff = checked_add(interest, balance)?;
+
+        let (payment, principal) = if period == periods || regular_payment >= payoff {
+            // The last payment covers whatever is left, so that rounding
+            // differences from earlier periods don't leave a residue. An
+            // earlier one only does if that's less than the regular payment.
+            (payoff, balance)
+        } else {
+            (regular_payment, checked_sub(regular_payment, interest)?)
+        };
+
+        balance = checked_sub(balance, principal)?;
+
+        rows.push(AmortizationRow {
+            period,
+            payment,
+            interest,
+            principal,
+            balance,
+        });
+
+        if balance.0 == 0 {
+            break;
+        }
+    }
+
+    Some(rows)
+}
+
+/// Raise a RocDec to a nonnegative integer power by repeated squaring, or
+/// None if any intermediate value doesn't fit in a RocDec.
+fn pow(base: RocDec, exp: u32) -> Option<RocDec> {
+    let mut answer = RocDec(RocDec::ONE_POINT_ZERO);
+    let mut base = base;
+    let mut exp = exp;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            answer = checked_mul(answer, base)?;
+        }
+
+        exp >>= 1;
+
+        if exp > 0 {
+            base = checked_mul(base, base)?;
+        }
+    }
+
+    Some(answer)
+}
+
+fn checked_add(a: RocDec, b: RocDec) -> Option<RocDec> {
+    a.0.checked_add(b.0).map(RocDec)
+}
+
+fn checked_sub(a: RocDec, b: RocDec) -> Option<RocDec> {
+    a.0.checked_sub(b.0).map(RocDec)
+}
+
+/// `a * b`, truncated toward zero like RocDec's own multiplication, or None
+/// if it doesn't fit in a RocDec.
+fn checked_mul(a: RocDec, b: RocDec) -> Option<RocDec> {
+    let mut acc = RocDecAccumulator::new();
+
+    acc.add_product(a, b);
+    acc.finish()
+}
+
+/// `a / b`, truncated toward zero like RocDec's own division, or None if `b`
+/// is zero or the answer doesn't fit in a RocDec.
+fn checked_div(a: RocDec, b: RocDec) -> Option<RocDec> {
+    let mut acc = RocDecAccumulator::new();
+
+    acc.add(a);
+    acc.checked_div(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{payment, schedule};
+    use crate::{RocDec, RoundingMode};
+    use std::convert::TryInto;
+
+    fn dec(string: &str) -> RocDec {
+        string.try_into().unwrap()
+    }
+
+    fn assert_ties_out(principal: &str, rate: &str, periods: u32) {
+        let principal = dec(principal);
+        let rows = schedule(principal, dec(rate), periods, 2, RoundingMode::HalfUp).unwrap();
+
+        assert!(rows.len() <= periods as usize);
+        assert_eq!(RocDec(0), rows.last().unwrap().balance);
+
+        let mut total_principal = RocDec(0);
+
+        for row in rows.iter() {
+            assert_eq!(row.payment, row.interest + row.principal);
+            assert!(row.balance >= RocDec(0));
+            assert_eq!(row.interest, row.interest.round(2, RoundingMode::Down));
+
+            total_principal = total_principal + row.principal;
+        }
+
+        assert_eq!(principal, total_principal);
+    }
+
+    #[test]
+    fn payment_30_year_mortgage() {
+        // $100,000 at 6% a year, paid monthly.
+        assert_eq!(
+            "599.55",
+            payment(dec("100000.0"), dec("0.005"), 360, 2, RoundingMode::HalfUp)
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn payment_one_year() {
+        assert_eq!(
+            "88.85",
+            payment(dec("1000.0"), dec("0.01"), 12, 2, RoundingMode::HalfUp)
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(
+            "88.84",
+            payment(dec("1000.0"), dec("0.01"), 12, 2, RoundingMode::Down)
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn payment_zero_rate() {
+        assert_eq!(
+            "33.34",
+            payment(dec("100.0"), dec("0.0"), 3, 2, RoundingMode::Up)
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn schedule_rows() {
+        let rows = schedule(dec("1000.0"), dec("0.01"), 12, 2, RoundingMode::HalfUp).unwrap();
+
+        let first = rows[0];
+        assert_eq!(1, first.period);
+        assert_eq!("88.85", first.payment.to_string());
+        assert_eq!("10.0", first.interest.to_string());
+        assert_eq!("78.85", first.principal.to_string());
+        assert_eq!("921.15", first.balance.to_string());
+
+        // The final payment absorbs the rounding of the earlier ones.
+        let last = rows[11];
+        assert_eq!(12, last.period);
+        assert_eq!("88.84", last.payment.to_string());
+        assert_eq!("0.88", last.interest.to_string());
+        assert_eq!("0.0", last.balance.to_string());
+    }
+
+    #[test]
+    fn schedule_ties_out() {
+        assert_ties_out("1000.0", "0.01", 12);
+        assert_ties_out("100000.0", "0.005", 360);
+        assert_ties_out("250000.0", "0.003125", 180);
+        assert_ties_out("100.0", "0.0", 3);
+        assert_ties_out("0.01", "0.02", 5);
+    }
+
+    #[test]
+    fn schedule_pays_off_early() {
+        // The payment of 0.002 rounds up to 0.01, which pays off the whole
+        // loan in the first period.
+        let rows = schedule(dec("0.01"), dec("0.0"), 5, 2, RoundingMode::Up).unwrap();
+
+        assert_eq!(1, rows.len());
+        assert_eq!("0.01", rows[0].payment.to_string());
+        assert_eq!("0.01", rows[0].principal.to_string());
+        assert_eq!("0.0", rows[0].balance.to_string());
+
+        let rows = schedule(dec("0.05"), dec("0.0"), 4, 2, RoundingMode::Up).unwrap();
+        let payments: Vec<String> = rows.iter().map(|row| row.payment.to_string()).collect();
+
+        assert_eq!(vec!["0.02", "0.02", "0.01"], payments);
+
+        assert_ties_out("0.01", "0.0", 5);
+        assert_ties_out("0.05", "0.0", 4);
+        assert_ties_out("0.03", "0.01", 7);
+    }
+
+    #[test]
+    fn schedule_no_periods() {
+        assert_eq!(
+            Some(vec![]),
+            schedule(dec("1000.0"), dec("0.01"), 0, 2, RoundingMode::HalfUp)
+        );
+    }
+
+    #[test]
+    fn payment_overflow() {
+        assert_eq!(
+            None,
+            payment(RocDec::MAX, dec("0.0"), 1, 0, RoundingMode::Up)
+        );
+        assert_eq!(
+            None,
+            schedule(RocDec::MAX, dec("0.0"), 1, 0, RoundingMode::Up)
+        );
+    }
+
+    #[test]
+    fn payment_long_term() {
+        // 1.01^5000 is about 4 * 10^21, which is past RocDec::MAX.
+        assert_eq!(
+            None,
+            payment(dec("1000.0"), dec("0.01"), 5000, 2, RoundingMode::HalfUp)
+        );
+        assert_eq!(
+            None,
+            schedule(dec("1000.0"), dec("0.01"), 5000, 2, RoundingMode::HalfUp)
+        );
+    }
+
+    #[test]
+    fn payment_degenerate_rate() {
+        // (1 - 2)^4 is 1, so the formula would divide by zero.
+        assert_eq!(
+            None,
+            payment(dec("1000.0"), dec("-2.0"), 4, 2, RoundingMode::HalfUp)
+        );
+        assert_eq!(
+            None,
+            schedule(dec("1000.0"), dec("-2.0"), 4, 2, RoundingMode::HalfUp)
+        );
+        assert_eq!(
+            None,
+            payment(dec("1000.0"), RocDec::MAX, 12, 2, RoundingMode::HalfUp)
+        );
+    }
+}
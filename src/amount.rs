@@ -0,0 +1,337 @@
+use crate::parse::scan;
+use crate::{ParseErrorKind, ParseOptions, ParseRocDecError, RocDec};
+
+/// A formatted amount, as parsed by `RocDec::parse_amount`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Amount<'a> {
+    /// The amount itself. For percentages, this has already been divided
+    /// by 100, so "12.5%" is 0.125.
+    pub value: RocDec,
+    /// The currency symbol or ISO code, e.g. "$", "US$" or "EUR", exactly
+    /// as it appeared in the string.
+    pub currency: Option<&'a str>,
+    /// Whether the amount had a "%" after it.
+    pub is_percent: bool,
+}
+
+/// Currency symbols recognized on their own, or after one or two capital
+/// letters which say whose currency it is, e.g. "US$", "HK$" or "R$".
+const CURRENCY_SYMBOLS: &[char] = &[
+    '$', '¢', '£', '¥', '€', '₡', '₦', '₩', '₪', '₫', '₱', '₲', '₴', '₵', '₸', '₹', '₺', '₼', '₽',
+    '₾', '฿',
+];
+
+/// Characters which group digits in some locales, other than '.' and ','.
+const GROUP_SEPARATORS: &[char] = &[' ', '\'', '\u{a0}', '\u{202f}'];
+
+impl RocDec {
+    /// Parse an amount the way a person would write it, e.g. in a
+    /// spreadsheet cell: "$1,234.56", "EUR 12,50", "12.5%" or "(45.00)".
+    ///
+    /// This accepts:
+    ///
+    /// - A currency symbol or a three-letter ISO code, either before or
+    ///   after the number, with or without a space in between.
+    /// - A "%" after the number, in which case the value is divided by 100.
+    ///   This is exact, so it fails if it would need more than
+    ///   DECIMAL_PLACES decimal places.
+    /// - A sign, either before or after the currency, or parentheses for
+    ///   an accounting-style negative amount.
+    /// - Whitespace at the start and end.
+    ///
+    /// The decimal and group separators are inferred from the number. If
+    /// it has both '.' and ',', whichever comes last is the decimal point.
+    /// If it has only one of them, it's a group separator if it appears
+    /// more than once, and a decimal point otherwise - except that a single
+    /// ',' followed by exactly three digits is taken as a group separator,
+    /// so "1,234" is 1234 but "12,50" is 12.5. Spaces and apostrophes are
+    /// also accepted as group separators, e.g. "1 234,56". Every group after
+    /// the first must have three digits.
+    pub fn parse_amount(value: &str) -> Result<Amount<'_>, ParseRocDecError> {
+        let mut start = value.len() - value.trim_start().len();
+        let mut end = value.trim_end().len().max(start);
+
+        if start == end {
+            return Err(ParseRocDecError::new(ParseErrorKind::Empty, start));
+        }
+
+        let mut is_negative = false;
+        let mut has_sign = false;
+
+        if let Some((inner_start, inner_end)) = parenthesized(value, start, end) {
+            is_negative = true;
+            has_sign = true;
+            start = inner_start;
+            end = inner_end;
+        }
+
+        // A sign in front of the currency, e.g. "-$5". (A sign after the
+        // currency, e.g. "$-5", is part of the number.)
+        if !has_sign
+            && matches!(value.as_bytes()[start], b'+' | b'-')
+            && currency_prefix_len(&value[start + 1..end]).is_some()
+        {
+            is_negative = value.as_bytes()[start] == b'-';
+            has_sign = true;
+            start += 1;
+        }
+
+        let mut currency = None;
+
+        if let Some(len) = currency_prefix_len(&value[start..end]) {
+            currency = Some(&value[start..start + len]);
+            start = skip_whitespace(value, start + len, end);
+
+            // The parentheses can go inside the currency too, e.g. "$(5)".
+            if !has_sign {
+                if let Some((inner_start, inner_end)) = parenthesized(value, start, end) {
+                    is_negative = true;
+                    has_sign = true;
+                    start = inner_start;
+                    end = inner_end;
+                }
+            }
+        }
+
+        let is_percent = value[start..end].ends_with('%');
+
+        if is_percent {
+            if currency.is_some() {
+                return Err(ParseRocDecError::new(ParseErrorKind::InvalidDigit, end - 1));
+            }
+
+            end = value[start..end - 1].trim_end().len() + start;
+        } else if currency.is_none() {
+            if let Some(len) = currency_suffix_len(&value[start..end]) {
+                currency = Some(&value[end - len..end]);
+                end = value[start..end - len].trim_end().len() + start;
+            }
+        }
+
+        if has_sign && matches!(value.as_bytes().get(start), Some(b'+') | Some(b'-')) {
+            return Err(ParseRocDecError::new(ParseErrorKind::InvalidDigit, start));
+        }
+
+        let number = &value[start..end];
+        let shift_offset =
+            |err: ParseRocDecError| ParseRocDecError::new(err.kind(), err.offset() + start);
+
+        let mut number = scan(number.as_bytes(), &infer_options(number)).map_err(shift_offset)?;
+
+        number.is_negative |= is_negative;
+
+        if is_percent {
+            number.exponent = number.exponent.saturating_sub(2);
+        }
+
+        let value = number.to_roc_dec().map_err(shift_offset)?;
+
+        Ok(Amount {
+            value,
+            currency,
+            is_percent,
+        })
+    }
+}
+
+/// If value[start..end] is wrapped in parentheses, the range inside them,
+/// without any whitespace just inside the parentheses.
+fn parenthesized(value: &str, start: usize, end: usize) -> Option<(usize, usize)> {
+    let inner = value[start..end].strip_prefix('(')?.strip_suffix(')')?;
+    let inner_end = start + 1 + inner.trim_end().len();
+
+    // Trimming the end first means that if there's nothing but whitespace
+    // inside, this is an empty range rather than a backwards one.
+    Some((skip_whitespace(value, start + 1, inner_end), inner_end))
+}
+
+fn skip_whitespace(value: &str, start: usize, end: usize) -> usize {
+    end - value[start..end].trim_start().len()
+}
+
+/// The length in bytes of the currency at the start of the string, if any.
+fn currency_prefix_len(value: &str) -> Option<usize> {
+    let len = value
+        .find(|c: char| !is_currency_char(c))
+        .unwrap_or(value.len());
+
+    if is_currency(&value[..len]) {
+        Some(len)
+    } else {
+        None
+    }
+}
+
+/// The length in bytes of the currency at the end of the string, if any.
+fn currency_suffix_len(value: &str) -> Option<usize> {
+    let start = value
+        .rfind(|c: char| !is_currency_char(c))
+        .map(|index| index + value[index..].chars().next().map_or(0, char::len_utf8))
+        .unwrap_or(0);
+
+    if is_currency(&value[start..]) {
+        Some(value.len() - start)
+    } else {
+        None
+    }
+}
+
+fn is_currency_char(c: char) -> bool {
+    c.is_ascii_uppercase() || CURRENCY_SYMBOLS.contains(&c)
+}
+
+/// Whether this is a three-letter ISO code like "USD", or a currency symbol
+/// with at most two capital letters in front of it, like "$" or "US$".
+fn is_currency(value: &str) -> bool {
+    let letters = value.bytes().take_while(u8::is_ascii_uppercase).count();
+    let mut symbols = value[letters..].chars();
+
+    match (symbols.next(), symbols.next()) {
+        (None, _) => letters == 3,
+        (Some(symbol), None) => letters <= 2 && CURRENCY_SYMBOLS.contains(&symbol),
+        (Some(_), Some(_)) => false,
+    }
+}
+
+/// Guess which separators the number uses. See `RocDec::parse_amount`.
+fn infer_options(number: &str) -> ParseOptions {
+    let last_point = number.rfind('.');
+    let last_comma = number.rfind(',');
+
+    let (decimal_separator, group_separator) = match (last_point, last_comma) {
+        (Some(point), Some(comma)) if point > comma => ('.', Some(',')),
+        (Some(_), Some(_)) => (',', Some('.')),
+        (Some(_), None) if number.matches('.').count() > 1 => (',', Some('.')),
+        (None, Some(comma)) if number.matches(',').count() > 1 || is_thousands(number, comma) => {
+            ('.', Some(','))
+        }
+        (None, Some(_)) => (',', None),
+        _ => ('.', None),
+    };
+
+    let group_separator =
+        group_separator.or_else(|| number.chars().find(|c| GROUP_SEPARATORS.contains(c)));
+
+    let options = ParseOptions::new().decimal_separator(decimal_separator);
+
+    match group_separator {
+        Some(separator) => options.group_separator(separator),
+        None => options,
+    }
+}
+
+/// Whether the only comma in the number, at `comma`, looks like a thousands
+/// separator: it has exactly three digits after it, and isn't just after a
+/// leading zero, as in "0,125".
+fn is_thousands(number: &str, comma: usize) -> bool {
+    let before = number[..comma].trim_start_matches(&['+', '-'][..]);
+    let after = &number[comma + 1..];
+
+    after.len() == 3 && after.bytes().all(|byte| byte.is_ascii_digit()) && before != "0"
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Amount, ParseErrorKind, RocDec};
+    use std::convert::TryInto;
+
+    fn dec(string: &str) -> RocDec {
+        string.try_into().unwrap()
+    }
+
+    fn assert_amount(string: &str, value: &str, currency: Option<&str>, is_percent: bool) {
+        let expected = Amount {
+            value: dec(value),
+            currency,
+            is_percent,
+        };
+
+        assert_eq!(Ok(expected), RocDec::parse_amount(string), "{:?}", string);
+    }
+
+    fn assert_amount_err(string: &str, kind: ParseErrorKind, offset: usize) {
+        let err = RocDec::parse_amount(string).unwrap_err();
+
+        assert_eq!((kind, offset), (err.kind(), err.offset()), "{:?}", string);
+    }
+
+    #[test]
+    fn plain_numbers() {
+        assert_amount("12.5", "12.5", None, false);
+        assert_amount("  -3  ", "-3", None, false);
+        assert_amount("1,234,567.89", "1234567.89", None, false);
+    }
+
+    #[test]
+    fn currency_symbols() {
+        assert_amount("$1,234.56", "1234.56", Some("$"), false);
+        assert_amount("€ 9.99", "9.99", Some("€"), false);
+        assert_amount("US$5", "5", Some("US$"), false);
+        assert_amount("12,50 €", "12.5", Some("€"), false);
+        assert_amount("100¥", "100", Some("¥"), false);
+    }
+
+    #[test]
+    fn iso_codes() {
+        assert_amount("EUR 12,50", "12.5", Some("EUR"), false);
+        assert_amount("12.50 USD", "12.5", Some("USD"), false);
+        assert_amount("CHF 1'234.50", "1234.5", Some("CHF"), false);
+        assert_amount("PLN1 234,56", "1234.56", Some("PLN"), false);
+    }
+
+    #[test]
+    fn inferred_separators() {
+        assert_amount("1.234,56", "1234.56", None, false);
+        assert_amount("1.234.567", "1234567", None, false);
+        assert_amount("1,234", "1234", None, false);
+        assert_amount("1.234", "1.234", None, false);
+        assert_amount("0,125", "0.125", None, false);
+        assert_amount("12,5", "12.5", None, false);
+        assert_amount("1,2345", "1.2345", None, false);
+    }
+
+    #[test]
+    fn negatives() {
+        assert_amount("-$5.00", "-5", Some("$"), false);
+        assert_amount("$-5.00", "-5", Some("$"), false);
+        assert_amount("(45.00)", "-45", None, false);
+        assert_amount("($1,000)", "-1000", Some("$"), false);
+        assert_amount("$( 1,000 )", "-1000", Some("$"), false);
+        assert_amount("(12.5%)", "-0.125", None, true);
+    }
+
+    #[test]
+    fn percentages() {
+        assert_amount("12.5%", "0.125", None, true);
+        assert_amount("-3 %", "-0.03", None, true);
+        assert_amount("100%", "1", None, true);
+        assert_amount("0.0000000000000001%", "0.000000000000000001", None, true);
+    }
+
+    #[test]
+    fn percentage_too_many_decimal_places() {
+        assert_amount_err(
+            "0.00000000000000001%",
+            ParseErrorKind::TooManyDecimalPlaces,
+            18,
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_amount_err("", ParseErrorKind::Empty, 0);
+        assert_amount_err("   ", ParseErrorKind::Empty, 3);
+        assert_amount_err("()", ParseErrorKind::Empty, 1);
+        assert_amount_err("( )", ParseErrorKind::Empty, 1);
+        assert_amount_err("$( )", ParseErrorKind::Empty, 2);
+        assert_amount_err("( $ )", ParseErrorKind::Empty, 3);
+        assert_amount_err("$12%", ParseErrorKind::InvalidDigit, 3);
+        assert_amount_err("$5 EUR", ParseErrorKind::InvalidDigit, 2);
+        assert_amount_err("(-5)", ParseErrorKind::InvalidDigit, 1);
+        assert_amount_err("-$-5", ParseErrorKind::InvalidDigit, 2);
+        assert_amount_err("ABCD 5", ParseErrorKind::InvalidDigit, 0);
+        assert_amount_err("$1..5", ParseErrorKind::InvalidDigit, 2);
+        assert_amount_err("1.2.3", ParseErrorKind::InvalidDigit, 1);
+        assert_amount_err("$ 1e30", ParseErrorKind::Overflow, 2);
+    }
+}
@@ -0,0 +1,561 @@
+use crate::{round_magnitude, RocDec, RoundingMode};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The reason a value could not be converted to or from a RocDec.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ConversionError {
+    /// The value was too big or too small to fit in the target type.
+    OutOfRange,
+    /// The value can't be represented exactly in the target type, e.g. a
+    /// RocDec with a fractional part being converted to an integer.
+    Inexact,
+    /// The value was NaN or infinite.
+    NotFinite,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            ConversionError::OutOfRange => "value is out of range for the target type",
+            ConversionError::Inexact => "value cannot be represented exactly in the target type",
+            ConversionError::NotFinite => "value is not finite",
+        };
+
+        f.write_str(description)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Every one of these fits, because RocDec's integer part goes up to about
+/// 1.7 * 10^20, and u64::MAX is only about 1.8 * 10^19.
+macro_rules! impl_from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for RocDec {
+                #[inline(always)]
+                fn from(num: $int) -> Self {
+                    RocDec(num as i128 * RocDec::ONE_POINT_ZERO)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl TryFrom<i128> for RocDec {
+    type Error = ConversionError;
+
+    fn try_from(num: i128) -> Result<Self, Self::Error> {
+        num.checked_mul(RocDec::ONE_POINT_ZERO)
+            .map(RocDec)
+            .ok_or(ConversionError::OutOfRange)
+    }
+}
+
+impl TryFrom<u128> for RocDec {
+    type Error = ConversionError;
+
+    fn try_from(num: u128) -> Result<Self, Self::Error> {
+        match i128::try_from(num) {
+            Ok(num) => RocDec::try_from(num),
+            Err(_) => Err(ConversionError::OutOfRange),
+        }
+    }
+}
+
+impl RocDec {
+    /// Round to a whole number with the given mode. Every RocDec's integer
+    /// part fits in an i128, so this can't fail.
+    pub fn to_i128(&self, mode: RoundingMode) -> i128 {
+        let is_negative = self.0.is_negative();
+        let magnitude = round_magnitude(self.0.unsigned_abs(), is_negative, 0, mode);
+
+        // This is at most about 1.7 * 10^20, so it fits in an i128.
+        let int = (magnitude / Self::ONE_POINT_ZERO as u128) as i128;
+
+        if is_negative {
+            -int
+        } else {
+            int
+        }
+    }
+
+    /// Round to a whole number with the given mode, or return an error if
+    /// the answer doesn't fit in an i64.
+    pub fn to_i64(&self, mode: RoundingMode) -> Result<i64, ConversionError> {
+        i64::try_from(self.to_i128(mode)).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Round to a whole number with the given mode, or return an error if
+    /// the answer doesn't fit in a u64, e.g. if it's negative.
+    pub fn to_u64(&self, mode: RoundingMode) -> Result<u64, ConversionError> {
+        u64::try_from(self.to_i128(mode)).map_err(|_| ConversionError::OutOfRange)
+    }
+}
+
+/// These are strict: a value with a nonzero fractional part is an error
+/// rather than being rounded. Use `to_i128` and friends to round instead.
+macro_rules! impl_try_from_roc_dec {
+    ($($int:ty),*) => {
+        $(
+            impl TryFrom<RocDec> for $int {
+                type Error = ConversionError;
+
+                fn try_from(dec: RocDec) -> Result<Self, Self::Error> {
+                    if dec.0 % RocDec::ONE_POINT_ZERO != 0 {
+                        return Err(ConversionError::Inexact);
+                    }
+
+                    <$int>::try_from(dec.0 / RocDec::ONE_POINT_ZERO)
+                        .map_err(|_| ConversionError::OutOfRange)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_roc_dec!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl RocDec {
+    /// The nearest f64 to this value.
+    ///
+    /// Dividing the raw i128 by 10^18 as floats would round twice, once for
+    /// each conversion, and can end up one float away from the right answer.
+    /// Parsing the decimal string rounds only once, and Rust's float parsing
+    /// is correctly rounded.
+    pub fn to_f64(&self) -> f64 {
+        let mut buf = [0; Self::MAX_STR_LEN];
+
+        // Every string a RocDec formats to is a valid float literal.
+        self.to_str_buf(&mut buf).parse().unwrap_or(f64::NAN)
+    }
+
+    /// The nearest f32 to this value. This parses the decimal string
+    /// directly, rather than rounding `to_f64`'s answer a second time.
+    pub fn to_f32(&self) -> f32 {
+        let mut buf = [0; Self::MAX_STR_LEN];
+
+        self.to_str_buf(&mut buf).parse().unwrap_or(f32::NAN)
+    }
+
+    /// The float's exact value rounded to 18 decimal places with the given
+    /// mode, or an error if it's NaN, infinite, or doesn't fit in a RocDec.
+    ///
+    /// Note that this rounds the float's exact binary value, so e.g. 0.1
+    /// (which is really 0.1000000000000000055511151231257827...) becomes
+    /// 0.100000000000000006 rather than 0.1.
+    pub fn from_f64(value: f64, mode: RoundingMode) -> Result<RocDec, ConversionError> {
+        from_float(value, mode).map(|(dec, _)| dec)
+    }
+
+    /// The float's exact value rounded to 18 decimal places with the given
+    /// mode, or an error if it's NaN, infinite, or doesn't fit in a RocDec.
+    pub fn from_f32(value: f32, mode: RoundingMode) -> Result<RocDec, ConversionError> {
+        // Every f32 converts to an f64 exactly, so this only rounds once.
+        Self::from_f64(value as f64, mode)
+    }
+}
+
+/// These are strict: if the float's exact value has more than 18 decimal
+/// places, that's an error rather than being rounded. Use `from_f64` or
+/// `from_f32` to round instead.
+impl TryFrom<f64> for RocDec {
+    type Error = ConversionError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        match from_float(value, RoundingMode::Down)? {
+            (dec, false) => Ok(dec),
+            (_, true) => Err(ConversionError::Inexact),
+        }
+    }
+}
+
+impl TryFrom<f32> for RocDec {
+    type Error = ConversionError;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        RocDec::try_from(value as f64)
+    }
+}
+
+/// Round a float's exact value to a RocDec, and say whether that was inexact.
+fn from_float(value: f64, mode: RoundingMode) -> Result<(RocDec, bool), ConversionError> {
+    if !value.is_finite() {
+        return Err(ConversionError::NotFinite);
+    }
+
+    // The float's exact value is mantissa * 2^exponent.
+    let bits = value.to_bits();
+    let is_negative = value.is_sign_negative();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = (bits & ((1 << 52) - 1)) as u128;
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        // Subnormal, with no implicit leading 1.
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased_exponent - 1075)
+    };
+
+    let (magnitude, inexact) = if exponent >= 0 {
+        // A whole number, so it's either exact or too big.
+        let exponent = exponent as u32;
+
+        if exponent > mantissa.leading_zeros() {
+            return Err(ConversionError::OutOfRange);
+        }
+
+        match (mantissa << exponent).checked_mul(RocDec::ONE_POINT_ZERO as u128) {
+            Some(magnitude) => (magnitude, false),
+            None => return Err(ConversionError::OutOfRange),
+        }
+    } else {
+        // The mantissa is at most 2^53 and 10^18 is less than 2^60, so
+        // this fits easily.
+        let scaled = mantissa * RocDec::ONE_POINT_ZERO as u128;
+        let shift = exponent.unsigned_abs();
+
+        let (truncated, discarded, inexact) = if shift >= 128 {
+            // scaled is less than 2^113, so all of it is discarded, and
+            // that's less than half of 2^shift.
+            (0, Ordering::Less, scaled != 0)
+        } else {
+            let rem = scaled & ((1 << shift) - 1);
+
+            (scaled >> shift, rem.cmp(&(1 << (shift - 1))), rem != 0)
+        };
+
+        if mode.rounds_away(is_negative, truncated % 2 == 1, discarded, inexact) {
+            (truncated + 1, true)
+        } else {
+            (truncated, inexact)
+        }
+    };
+
+    // RocDec::MIN's magnitude doesn't fit in an i128, but that's fine,
+    // because no float is within 10^-18 of RocDec::MIN.
+    let magnitude = i128::try_from(magnitude).map_err(|_| ConversionError::OutOfRange)?;
+
+    Ok((
+        RocDec(if is_negative { -magnitude } else { magnitude }),
+        inexact,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConversionError;
+    use crate::{RocDec, RoundingMode};
+    use std::convert::{TryFrom, TryInto};
+
+    fn dec(string: &str) -> RocDec {
+        string.try_into().unwrap()
+    }
+
+    #[test]
+    fn from_small_ints() {
+        assert_eq!(dec("0.0"), RocDec::from(0u8));
+        assert_eq!(dec("255.0"), RocDec::from(u8::MAX));
+        assert_eq!(dec("-128.0"), RocDec::from(i8::MIN));
+        assert_eq!(dec("-32768.0"), RocDec::from(i16::MIN));
+        assert_eq!(dec("65535.0"), RocDec::from(u16::MAX));
+        assert_eq!(dec("-2147483648.0"), RocDec::from(i32::MIN));
+        assert_eq!(dec("4294967295.0"), RocDec::from(u32::MAX));
+    }
+
+    #[test]
+    fn from_64_bit_ints() {
+        assert_eq!(dec("-9223372036854775808.0"), RocDec::from(i64::MIN));
+        assert_eq!(dec("9223372036854775807.0"), RocDec::from(i64::MAX));
+        assert_eq!(dec("18446744073709551615.0"), RocDec::from(u64::MAX));
+        assert_eq!(dec("-1.0"), (-1i64).into());
+    }
+
+    #[test]
+    fn try_from_i128() {
+        assert_eq!(Ok(dec("-42.0")), RocDec::try_from(-42i128));
+        assert_eq!(
+            Ok(dec("170141183460469231731.0")),
+            RocDec::try_from(170_141_183_460_469_231_731i128)
+        );
+        assert_eq!(
+            Ok(dec("-170141183460469231731.0")),
+            RocDec::try_from(-170_141_183_460_469_231_731i128)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::try_from(170_141_183_460_469_231_732i128)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::try_from(-170_141_183_460_469_231_732i128)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::try_from(i128::MIN)
+        );
+    }
+
+    #[test]
+    fn try_from_u128() {
+        assert_eq!(Ok(dec("7.0")), RocDec::try_from(7u128));
+        assert_eq!(
+            Ok(dec("170141183460469231731.0")),
+            RocDec::try_from(170_141_183_460_469_231_731u128)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::try_from(170_141_183_460_469_231_732u128)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::try_from(u128::MAX)
+        );
+    }
+
+    #[test]
+    fn error_display() {
+        assert_eq!(
+            "value is out of range for the target type",
+            ConversionError::OutOfRange.to_string()
+        );
+    }
+
+    #[test]
+    fn to_i128() {
+        assert_eq!(2, dec("2.5").to_i128(RoundingMode::HalfEven));
+        assert_eq!(3, dec("2.5").to_i128(RoundingMode::HalfUp));
+        assert_eq!(-2, dec("-2.5").to_i128(RoundingMode::HalfEven));
+        assert_eq!(-3, dec("-2.1").to_i128(RoundingMode::Floor));
+        assert_eq!(-2, dec("-2.9").to_i128(RoundingMode::Down));
+        assert_eq!(
+            3,
+            dec("2.000000000000000001").to_i128(RoundingMode::Ceiling)
+        );
+        assert_eq!(0, dec("-0.4").to_i128(RoundingMode::HalfEven));
+        assert_eq!(
+            170_141_183_460_469_231_732,
+            RocDec::MAX.to_i128(RoundingMode::Up)
+        );
+        assert_eq!(
+            -170_141_183_460_469_231_731,
+            RocDec::MIN.to_i128(RoundingMode::Down)
+        );
+        assert_eq!(
+            -170_141_183_460_469_231_732,
+            RocDec::MIN.to_i128(RoundingMode::Floor)
+        );
+    }
+
+    #[test]
+    fn to_i64() {
+        assert_eq!(Ok(-8), dec("-7.5").to_i64(RoundingMode::HalfEven));
+        assert_eq!(
+            Ok(i64::MAX),
+            RocDec::from(i64::MAX).to_i64(RoundingMode::Up)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            (RocDec::from(i64::MAX) + dec("0.5")).to_i64(RoundingMode::HalfUp)
+        );
+        assert_eq!(
+            Ok(i64::MAX),
+            (RocDec::from(i64::MAX) + dec("0.5")).to_i64(RoundingMode::HalfDown)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::MIN.to_i64(RoundingMode::Down)
+        );
+    }
+
+    #[test]
+    fn to_u64() {
+        assert_eq!(Ok(12), dec("12.345").to_u64(RoundingMode::HalfEven));
+        assert_eq!(Ok(0), dec("-0.4").to_u64(RoundingMode::HalfEven));
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            dec("-0.6").to_u64(RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Ok(u64::MAX),
+            RocDec::from(u64::MAX).to_u64(RoundingMode::Down)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::MAX.to_u64(RoundingMode::Down)
+        );
+    }
+
+    #[test]
+    fn try_into_ints() {
+        assert_eq!(Ok(-128i8), i8::try_from(dec("-128.0")));
+        assert_eq!(Err(ConversionError::OutOfRange), i8::try_from(dec("128.0")));
+        assert_eq!(Ok(65535u16), u16::try_from(dec("65535.0")));
+        assert_eq!(Err(ConversionError::OutOfRange), u32::try_from(dec("-1.0")));
+        assert_eq!(Ok(3usize), dec("3.0").try_into());
+        assert_eq!(Ok(-3isize), dec("-3.0").try_into());
+        assert_eq!(Ok(i64::MIN), i64::try_from(RocDec::from(i64::MIN)));
+        assert_eq!(
+            Ok(170_141_183_460_469_231_731u128),
+            u128::try_from(dec("170141183460469231731.0"))
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            u128::try_from(dec("-5.0"))
+        );
+    }
+
+    #[test]
+    fn try_into_ints_is_strict() {
+        assert_eq!(Err(ConversionError::Inexact), i32::try_from(dec("1.5")));
+        assert_eq!(Err(ConversionError::Inexact), i128::try_from(RocDec::MAX));
+        assert_eq!(Err(ConversionError::Inexact), i128::try_from(RocDec::MIN));
+        assert_eq!(
+            Err(ConversionError::Inexact),
+            u8::try_from(dec("0.000000000000000001"))
+        );
+        assert_eq!(Ok(0u8), u8::try_from(dec("-0.0")));
+    }
+
+    #[test]
+    fn to_f64() {
+        assert_eq!(0.0, dec("0.0").to_f64());
+        assert_eq!(0.1, dec("0.1").to_f64());
+        assert_eq!(-1234.5678, dec("-1234.5678").to_f64());
+        assert_eq!(1e-18, RocDec(1).to_f64());
+        assert_eq!(1.7014118346046924e20, RocDec::MAX.to_f64());
+        assert_eq!(-1.7014118346046924e20, RocDec::MIN.to_f64());
+
+        // Dividing as floats rounds twice, and gets this one wrong.
+        let dec = dec("57.515448340910453821");
+        assert_eq!(57.51544834091045, dec.to_f64());
+        assert_ne!(57.51544834091045, dec.0 as f64 / 1e18);
+    }
+
+    #[test]
+    fn to_f32() {
+        assert_eq!(0.1f32, dec("0.1").to_f32());
+        assert_eq!(-2.5f32, dec("-2.5").to_f32());
+        assert_eq!(1.701412e20f32, RocDec::MAX.to_f32());
+    }
+
+    #[test]
+    fn from_f64() {
+        assert_eq!(
+            Ok(dec("2.5")),
+            RocDec::from_f64(2.5, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Ok(dec("0.100000000000000006")),
+            RocDec::from_f64(0.1, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Ok(dec("0.100000000000000005")),
+            RocDec::from_f64(0.1, RoundingMode::Down)
+        );
+        assert_eq!(
+            Ok(dec("-0.100000000000000006")),
+            RocDec::from_f64(-0.1, RoundingMode::Floor)
+        );
+        assert_eq!(Ok(RocDec(0)), RocDec::from_f64(-0.0, RoundingMode::Up));
+        assert_eq!(
+            Ok(RocDec(0)),
+            RocDec::from_f64(1e-300, RoundingMode::HalfUp)
+        );
+        assert_eq!(Ok(RocDec(1)), RocDec::from_f64(1e-300, RoundingMode::Up));
+        assert_eq!(
+            Ok(RocDec(-1)),
+            RocDec::from_f64(-5e-324, RoundingMode::Floor)
+        );
+        // 2^-60 is about 0.87 * 10^-18.
+        assert_eq!(
+            Ok(RocDec(1)),
+            RocDec::from_f64(2f64.powi(-60), RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Ok(dec("1099511627776.0")),
+            RocDec::from_f64(2f64.powi(40), RoundingMode::HalfEven)
+        );
+    }
+
+    #[test]
+    fn from_f64_range() {
+        // The nearest f64 to RocDec::MAX is just past it.
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::from_f64(RocDec::MAX.to_f64(), RoundingMode::Down)
+        );
+        assert_eq!(
+            Ok(dec("170141183460469211136.0")),
+            RocDec::from_f64(1.701411834604692e20, RoundingMode::Down)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::from_f64(1.8e20, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::from_f64(-1e300, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Err(ConversionError::OutOfRange),
+            RocDec::from_f64(f64::MAX, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Err(ConversionError::NotFinite),
+            RocDec::from_f64(f64::NAN, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Err(ConversionError::NotFinite),
+            RocDec::from_f64(f64::NEG_INFINITY, RoundingMode::HalfEven)
+        );
+    }
+
+    #[test]
+    fn from_f32() {
+        assert_eq!(Ok(dec("0.75")), RocDec::from_f32(0.75, RoundingMode::Down));
+        // 0.1f32 is really 0.100000001490116119384765625.
+        assert_eq!(
+            Ok(dec("0.100000001490116119")),
+            RocDec::from_f32(0.1, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Err(ConversionError::NotFinite),
+            RocDec::from_f32(f32::INFINITY, RoundingMode::HalfEven)
+        );
+    }
+
+    #[test]
+    fn try_from_floats_is_strict() {
+        assert_eq!(Ok(dec("-2.5")), RocDec::try_from(-2.5f64));
+        assert_eq!(Ok(dec("0.125")), RocDec::try_from(0.125f32));
+        // 2^-18 has exactly 18 decimal places, and 2^-19 has 19.
+        assert_eq!(
+            Ok(dec("0.000003814697265625")),
+            RocDec::try_from(2f64.powi(-18))
+        );
+        assert_eq!(
+            Err(ConversionError::Inexact),
+            RocDec::try_from(2f64.powi(-19))
+        );
+        assert_eq!(Err(ConversionError::Inexact), RocDec::try_from(0.1f64));
+        assert_eq!(Err(ConversionError::Inexact), RocDec::try_from(0.1f32));
+        assert_eq!(Err(ConversionError::Inexact), RocDec::try_from(1e-300));
+        assert_eq!(Err(ConversionError::OutOfRange), RocDec::try_from(1e21f64));
+        assert_eq!(Err(ConversionError::NotFinite), RocDec::try_from(f64::NAN));
+    }
+
+    #[test]
+    fn floats_round_trip() {
+        for string in &["0.0", "1.5", "-1234.5678", "0.001", "98765.4321"] {
+            let dec = dec(string);
+
+            let float = RocDec::from_f64(dec.to_f64(), RoundingMode::HalfEven).unwrap();
+
+            assert_eq!(dec, float.round(9, RoundingMode::HalfEven));
+        }
+    }
+}
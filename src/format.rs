@@ -0,0 +1,697 @@
+use crate::parse::POWERS_OF_TEN;
+use crate::{round_magnitude, RocDec, RoundingMode};
+use std::convert::TryFrom;
+use std::fmt::{self, Write};
+use std::ops::Deref;
+
+impl RocDec {
+    /// The length of the longest string a RocDec can format to, which is
+    /// the length of RocDec::MIN's.
+    pub const MAX_STR_LEN: usize = 41;
+
+    /// Format into a buffer on the stack, and return the part of it which
+    /// was used. This gives the same string as `to_string`, without
+    /// allocating.
+    pub fn to_str_buf<'a>(&self, buf: &'a mut [u8; Self::MAX_STR_LEN]) -> &'a str {
+        let magnitude = self.0.unsigned_abs();
+
+        if magnitude == 0 {
+            return "0.0";
+        }
+
+        let one = Self::ONE_POINT_ZERO as u64;
+
+        // Dividing a u128 is much slower than dividing a u64, and most
+        // numbers (anything below about 18.4) fit in one.
+        let (int_part, mut frac_part) = match u64::try_from(magnitude) {
+            Ok(small) => ((small / one) as u128, small % one),
+            Err(_) => (magnitude / one as u128, (magnitude % one as u128) as u64),
+        };
+        let mut len = 0;
+
+        if self.0.is_negative() {
+            buf[0] = b'-';
+            len = 1;
+        }
+
+        if int_part < U64_CHUNK {
+            let int_part = int_part as u64;
+
+            len = write_digits(buf, len, int_part, digit_count(int_part));
+        } else {
+            // The integer part can have up to 21 digits, which is too many
+            // for a u64, so split off everything above the lowest 19 digits
+            // first. There are at most two of those, and the lower 19 need
+            // their leading zeroes.
+            let high = (int_part / U64_CHUNK) as u64;
+            let low = (int_part % U64_CHUNK) as u64;
+
+            len = write_digits(buf, len, high, digit_count(high));
+            len = write_digits(buf, len, low, U64_CHUNK_DIGITS);
+        }
+
+        buf[len] = b'.';
+        len += 1;
+
+        // Trailing zeroes aren't shown, but there's always at least one
+        // digit after the decimal point.
+        let mut frac_digits = Self::DECIMAL_PLACES as usize;
+
+        if frac_part == 0 {
+            frac_digits = 1;
+        } else {
+            while frac_part % 100 == 0 {
+                frac_part /= 100;
+                frac_digits -= 2;
+            }
+
+            if frac_part % 10 == 0 {
+                frac_part /= 10;
+                frac_digits -= 1;
+            }
+        }
+
+        len = write_digits(buf, len, frac_part, frac_digits);
+
+        // Everything we wrote was ASCII, so this can't fail.
+        std::str::from_utf8(&buf[..len]).unwrap()
+    }
+
+    /// Format with exactly `places` digits after the decimal point, e.g.
+    /// "12.50", rounding with the given mode if there are more than that.
+    /// If `places` is 0, there's no decimal point either.
+    pub fn to_string_dp(&self, places: u32, mode: RoundingMode) -> String {
+        let (is_negative, digits) = self.fixed_places(places as usize, mode);
+
+        if is_negative {
+            format!("-{}", digits)
+        } else {
+            digits
+        }
+    }
+
+    /// Format with exactly `places` digits after the decimal point, rounding
+    /// ties to even. This is the same as `format!("{:.places$}", dec)`.
+    pub fn to_string_padded(&self, places: u32) -> String {
+        self.to_string_dp(places, RoundingMode::HalfEven)
+    }
+
+    /// Round to `places` decimal places, and return whether the result is
+    /// negative along with its digits. There's no such thing as -0.0, so a
+    /// negative number which rounds to zero isn't negative anymore.
+    pub(crate) fn fixed_places(&self, places: usize, mode: RoundingMode) -> (bool, String) {
+        let is_negative = self.0.is_negative();
+        let magnitude = round_magnitude(
+            self.0.unsigned_abs(),
+            is_negative,
+            places.min(Self::DECIMAL_PLACES as usize) as u32,
+            mode,
+        );
+
+        (
+            is_negative && magnitude != 0,
+            fixed_places_digits(magnitude, places),
+        )
+    }
+
+    /// Write the same string as `to_string` into `w`, without allocating.
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        w.write_str(self.to_str_buf(&mut [0; Self::MAX_STR_LEN]))
+    }
+
+    /// The same string as `to_string`, stored inline rather than on the heap.
+    pub fn to_dec_str(&self) -> DecStr {
+        let mut buf = [0; Self::MAX_STR_LEN];
+        let string = self.to_str_buf(&mut buf);
+
+        // to_str_buf doesn't always write into the buffer (e.g. for 0.0),
+        // so copy whatever it returned to the start of a fresh one.
+        let mut bytes = [0; Self::MAX_STR_LEN];
+
+        // DecStr's Deref relies on this.
+        debug_assert!(string.is_ascii());
+
+        bytes[..string.len()].copy_from_slice(string.as_bytes());
+
+        DecStr {
+            bytes,
+            len: string.len() as u8,
+        }
+    }
+}
+
+/// A formatted RocDec which doesn't need a heap allocation. It derefs to a
+/// `&str`, so it can be used anywhere one can.
+///
+/// The first `len` bytes are always ASCII, because they're copied from
+/// `to_str_buf`, which only ever writes a minus sign, digits and a decimal
+/// point.
+#[derive(Copy, Clone)]
+pub struct DecStr {
+    bytes: [u8; RocDec::MAX_STR_LEN],
+    len: u8,
+}
+
+impl Deref for DecStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        // SAFETY: These bytes are all ASCII (see DecStr's invariant), and
+        // ASCII is always valid UTF-8.
+        unsafe { std::str::from_utf8_unchecked(&self.bytes[..self.len as usize]) }
+    }
+}
+
+impl AsRef<str> for DecStr {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl PartialEq for DecStr {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for DecStr {}
+
+impl fmt::Display for DecStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self)
+    }
+}
+
+impl fmt::Debug for DecStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl From<RocDec> for String {
+    fn from(dec: RocDec) -> String {
+        dec.to_dec_str().to_string()
+    }
+}
+
+/// 10^19, the largest power of 10 which fits in a u64.
+const U64_CHUNK: u128 = 10_000_000_000_000_000_000;
+const U64_CHUNK_DIGITS: usize = 19;
+
+/// "00", "01", ..., "99", so that digits can be written two at a time.
+const DIGIT_PAIRS: [u8; 200] = {
+    let mut pairs = [0; 200];
+    let mut index = 0;
+
+    while index < 100 {
+        pairs[index * 2] = b'0' + (index / 10) as u8;
+        pairs[index * 2 + 1] = b'0' + (index % 10) as u8;
+        index += 1;
+    }
+
+    pairs
+};
+
+/// How many digits it takes to write this number, which is at least 1.
+fn digit_count(num: u64) -> usize {
+    let mut count = 1;
+    let mut threshold = 10u64;
+
+    while count < 20 && num >= threshold {
+        count += 1;
+        threshold = threshold.wrapping_mul(10);
+    }
+
+    count
+}
+
+/// Write exactly `digits` digits of `num` at `buf[start..]`, with leading
+/// zeroes if needed, and return where they end. Since we know where the
+/// last digit goes, we can fill them in from there without reversing.
+fn write_digits(buf: &mut [u8], start: usize, mut num: u64, digits: usize) -> usize {
+    let end = start + digits;
+    let mut pos = end;
+
+    while pos >= start + 2 {
+        let pair = (num % 100) as usize * 2;
+
+        buf[pos - 2..pos].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+        num /= 100;
+        pos -= 2;
+    }
+
+    if pos > start {
+        buf[start] = b'0' + (num % 10) as u8;
+    }
+
+    end
+}
+
+/// Width, fill, alignment, and the `+` and `0` flags work the same way they
+/// do for integers. A precision rounds to that many decimal places (ties go
+/// to the even neighbor, like RoundingMode::HalfEven) and always shows
+/// exactly that many, so `format!("{:.2}", dec)` gives e.g. "12.50".
+///
+/// Without a precision, this gives the shortest string which parses back to
+/// the same RocDec, with at least one digit after the decimal point.
+impl fmt::Display for RocDec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let is_negative = self.0.is_negative();
+
+        match f.precision() {
+            None => {
+                let mut buf = [0; Self::MAX_STR_LEN];
+                let string = self.to_str_buf(&mut buf);
+                let digits = string.strip_prefix('-').unwrap_or(string);
+
+                f.pad_integral(!is_negative, "", digits)
+            }
+            Some(places) => {
+                let (is_negative, digits) = self.fixed_places(places, RoundingMode::HalfEven);
+
+                f.pad_integral(!is_negative, "", &digits)
+            }
+        }
+    }
+}
+
+/// The same as Display, e.g. "1.5" rather than "RocDec(1500000000000000000)".
+impl fmt::Debug for RocDec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// The digits of a magnitude (already rounded to at most `places` decimal
+/// places) with exactly `places` digits after the decimal point, and no
+/// decimal point at all if `places` is 0.
+fn fixed_places_digits(magnitude: u128, places: usize) -> String {
+    let one = RocDec::ONE_POINT_ZERO as u128;
+    let mut string = (magnitude / one).to_string();
+
+    if places > 0 {
+        let frac_digits = format!("{:018}", magnitude % one);
+        let kept = places.min(frac_digits.len());
+
+        string.push('.');
+        string.push_str(&frac_digits[..kept]);
+        string.extend(std::iter::repeat('0').take(places - kept));
+    }
+
+    string
+}
+
+/// Scientific notation, the same way f64 does it: "1.5e3", "1e-15" or "0e0".
+/// A precision is the number of digits after the decimal point, rounding
+/// ties to even, e.g. `format!("{:.2e}", dec)` gives "1.23e3".
+impl fmt::LowerExp for RocDec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_exp(f, 'e')
+    }
+}
+
+/// The same as LowerExp, but with an "E", e.g. "1.5E3".
+impl fmt::UpperExp for RocDec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_exp(f, 'E')
+    }
+}
+
+impl RocDec {
+    /// Engineering notation, which is scientific notation where the exponent
+    /// is always a multiple of 3, e.g. "12.5e-9" or "1.5e3".
+    ///
+    /// With `Some(significant_digits)`, this rounds to that many significant
+    /// digits (at least 1), ties to even, and always shows that many, e.g.
+    /// "12.50e-9" for 4. With None, it shows every digit it needs to, and
+    /// no trailing zeroes after the decimal point.
+    pub fn to_engineering_string(&self, significant_digits: Option<u32>) -> String {
+        let (is_negative, digits, exponent) = self.significant_digits(
+            significant_digits.map(|digits| digits as usize),
+            RoundingMode::HalfEven,
+        );
+
+        // Between 1 and 3 digits go before the decimal point.
+        let engineering_exponent = exponent.div_euclid(3) * 3;
+        let int_digits = (exponent - engineering_exponent + 1) as usize;
+        let mut string = String::with_capacity(digits.len() + 8);
+
+        if is_negative {
+            string.push('-');
+        }
+
+        if digits.len() <= int_digits {
+            string.push_str(&digits);
+            string.extend(std::iter::repeat('0').take(int_digits - digits.len()));
+        } else {
+            string.push_str(&digits[..int_digits]);
+            string.push('.');
+            string.push_str(&digits[int_digits..]);
+        }
+
+        // Writing to a String can't fail.
+        let _ = write!(string, "e{}", engineering_exponent);
+
+        string
+    }
+
+    fn fmt_exp(&self, f: &mut fmt::Formatter<'_>, marker: char) -> fmt::Result {
+        let significant = f.precision().map(|places| places.saturating_add(1));
+        let (is_negative, digits, exponent) =
+            self.significant_digits(significant, RoundingMode::HalfEven);
+        let mut string = String::with_capacity(digits.len() + 8);
+
+        string.push_str(&digits[..1]);
+
+        if digits.len() > 1 {
+            string.push('.');
+            string.push_str(&digits[1..]);
+        }
+
+        write!(string, "{}{}", marker, exponent)?;
+
+        f.pad_integral(!is_negative, "", &string)
+    }
+
+    /// The significant digits of the number, along with whether it's
+    /// negative and the power of 10 of the first digit.
+    ///
+    /// With `Some(significant)`, they're rounded to exactly that many digits
+    /// (at least 1), padded with zeroes if needed. With None, they're all
+    /// the nonzero digits and any zeroes between them. Zero has one digit,
+    /// "0", with a power of 0.
+    fn significant_digits(
+        &self,
+        significant: Option<usize>,
+        mode: RoundingMode,
+    ) -> (bool, String, i32) {
+        let is_negative = self.0.is_negative();
+        let magnitude = self.0.unsigned_abs();
+        let significant = significant.map(|digits| digits.max(1));
+
+        if magnitude == 0 {
+            return (false, "0".repeat(significant.unwrap_or(1)), 0);
+        }
+
+        let count = POWERS_OF_TEN
+            .iter()
+            .position(|power| magnitude < *power)
+            .unwrap_or(POWERS_OF_TEN.len());
+        let mut exponent = count as i32 - 1 - Self::DECIMAL_PLACES as i32;
+
+        let digits = match significant {
+            None => magnitude.to_string().trim_end_matches('0').to_string(),
+            Some(significant) if significant < count => {
+                let unit = POWERS_OF_TEN[count - significant];
+                let mut kept = magnitude / unit;
+                let rem = magnitude % unit;
+
+                // unit is always a multiple of 10, so unit / 2 is exactly half.
+                if mode.rounds_away(is_negative, kept % 2 == 1, rem.cmp(&(unit / 2)), rem != 0) {
+                    kept += 1;
+
+                    // Rounding 999 up to 1000 adds a digit, so drop a zero.
+                    if kept == POWERS_OF_TEN[significant] {
+                        kept /= 10;
+                        exponent += 1;
+                    }
+                }
+
+                kept.to_string()
+            }
+            Some(significant) => {
+                let mut digits = magnitude.to_string();
+
+                digits.extend(std::iter::repeat('0').take(significant - count));
+
+                digits
+            }
+        };
+
+        (is_negative, digits, exponent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RocDec, RoundingMode};
+    use std::convert::TryInto;
+    use std::fmt::Write;
+
+    fn dec(string: &str) -> RocDec {
+        string.try_into().unwrap()
+    }
+
+    /// What to_string should give, using i128's own formatting.
+    fn expected_string(num: i128) -> String {
+        let digits = format!("{:019}", num.unsigned_abs());
+        let (int_digits, frac_digits) = digits.split_at(digits.len() - 18);
+        let frac_digits = frac_digits.trim_end_matches('0');
+        let sign = if num < 0 { "-" } else { "" };
+
+        if frac_digits.is_empty() {
+            format!("{}{}.0", sign, int_digits)
+        } else {
+            format!("{}{}.{}", sign, int_digits, frac_digits)
+        }
+    }
+
+    #[test]
+    fn matches_integer_formatting() {
+        let mut power = 1i128;
+
+        for _ in 0..39 {
+            for num in &[power, power - 1, power + 1, power / 7 * 3] {
+                for num in &[*num, -*num] {
+                    assert_eq!(expected_string(*num), RocDec(*num).to_string());
+                }
+            }
+
+            power = power.saturating_mul(10);
+        }
+
+        assert_eq!(expected_string(i128::MAX), RocDec::MAX.to_string());
+        assert_eq!(expected_string(i128::MIN), RocDec::MIN.to_string());
+    }
+
+    #[test]
+    fn to_str_buf() {
+        let mut buf = [0; RocDec::MAX_STR_LEN];
+
+        for string in &[
+            "0.0",
+            "1.5",
+            "-0.000000000000000001",
+            "100.0",
+            crate::MIN_STR,
+            crate::MAX_STR,
+        ] {
+            assert_eq!(*string, dec(string).to_str_buf(&mut buf));
+        }
+    }
+
+    #[test]
+    fn write_to() {
+        let mut string = String::new();
+
+        dec("1.5").write_to(&mut string).unwrap();
+        string.push(' ');
+        dec("-20.0").write_to(&mut string).unwrap();
+
+        assert_eq!("1.5 -20.0", string);
+    }
+
+    #[test]
+    fn dec_str() {
+        assert_eq!("0.0", &*RocDec(0).to_dec_str());
+        assert_eq!("-12.25", &*dec("-12.25").to_dec_str());
+        assert_eq!(crate::MIN_STR, &*RocDec::MIN.to_dec_str());
+        assert_eq!(dec("1.5").to_dec_str(), dec("1.50").to_dec_str());
+        assert_eq!(6, dec("-12.25").to_dec_str().len());
+        assert_eq!("  1.5", format!("{:>5}", dec("1.5").to_dec_str()));
+        assert_eq!("\"1.5\"", format!("{:?}", dec("1.5").to_dec_str()));
+    }
+
+    #[test]
+    fn display_padding() {
+        assert_eq!("   1.5", format!("{:6}", dec("1.5")));
+        assert_eq!("1.5   ", format!("{:<6}", dec("1.5")));
+        assert_eq!("*-1.5*", format!("{:*^6}", dec("-1.5")));
+        assert_eq!("+1.5", format!("{:+}", dec("1.5")));
+        assert_eq!("-1.5", format!("{:+}", dec("-1.5")));
+        assert_eq!("-001.5", format!("{:06}", dec("-1.5")));
+        assert_eq!("+0.0", format!("{:+}", dec("0.0")));
+    }
+
+    #[test]
+    fn display_precision() {
+        assert_eq!("12.50", format!("{:.2}", dec("12.5")));
+        assert_eq!("12", format!("{:.0}", dec("12.4")));
+        assert_eq!("2", format!("{:.0}", dec("2.5")));
+        assert_eq!("4", format!("{:.0}", dec("3.5")));
+        assert_eq!("-0.12", format!("{:.2}", dec("-0.125")));
+        assert_eq!("0.00", format!("{:.2}", dec("-0.001")));
+        assert_eq!("  +1.250", format!("{:+8.3}", dec("1.25")));
+        assert_eq!(
+            "0.00000000000000000100",
+            format!("{:.20}", dec("0.000000000000000001"))
+        );
+        assert_eq!("-170141183460469231732", format!("{:.0}", RocDec::MIN));
+        assert_eq!("170141183460469231732", format!("{:.0}", RocDec::MAX));
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!("-1.5", format!("{:?}", dec("-1.5")));
+        assert_eq!("Some(0.25)", format!("{:?}", Some(dec("0.25"))));
+        assert_eq!("1.50", format!("{:.2?}", dec("1.5")));
+    }
+
+    #[test]
+    fn to_string_dp() {
+        assert_eq!("12.50", dec("12.5").to_string_dp(2, RoundingMode::HalfUp));
+        assert_eq!("12.35", dec("12.345").to_string_dp(2, RoundingMode::HalfUp));
+        assert_eq!(
+            "12.34",
+            dec("12.345").to_string_dp(2, RoundingMode::HalfEven)
+        );
+        assert_eq!("12.34", dec("12.349").to_string_dp(2, RoundingMode::Down));
+        assert_eq!(
+            "-12.35",
+            dec("-12.341").to_string_dp(2, RoundingMode::Floor)
+        );
+        assert_eq!(
+            "-12.34",
+            dec("-12.349").to_string_dp(2, RoundingMode::Ceiling)
+        );
+        assert_eq!("100", dec("99.5").to_string_dp(0, RoundingMode::HalfUp));
+        assert_eq!("0", dec("0.0").to_string_dp(0, RoundingMode::Up));
+        assert_eq!(
+            "0.000",
+            dec("-0.0004").to_string_dp(3, RoundingMode::HalfUp)
+        );
+        assert_eq!(
+            "1.50000000000000000000",
+            dec("1.5").to_string_dp(20, RoundingMode::Up)
+        );
+    }
+
+    #[test]
+    fn to_string_dp_extremes() {
+        assert_eq!(
+            "170141183460469231732",
+            RocDec::MAX.to_string_dp(0, RoundingMode::Up)
+        );
+        assert_eq!(
+            "-170141183460469231732",
+            RocDec::MIN.to_string_dp(0, RoundingMode::HalfUp)
+        );
+        assert_eq!(
+            "-170141183460469231731.687303715884105728",
+            RocDec::MIN.to_string_dp(18, RoundingMode::Up)
+        );
+    }
+
+    #[test]
+    fn to_string_padded() {
+        assert_eq!("12.50", dec("12.5").to_string_padded(2));
+        assert_eq!("0.12", dec("0.125").to_string_padded(2));
+        assert_eq!("-2", dec("-2.5").to_string_padded(0));
+        assert_eq!(
+            format!("{:.3}", dec("-7.0625")),
+            dec("-7.0625").to_string_padded(3)
+        );
+    }
+
+    #[test]
+    fn lower_exp() {
+        assert_eq!("1.5e3", format!("{:e}", dec("1500.0")));
+        assert_eq!("1e-15", format!("{:e}", dec("0.000000000000001")));
+        assert_eq!("-2.5e-1", format!("{:e}", dec("-0.25")));
+        assert_eq!("1e0", format!("{:e}", dec("1.0")));
+        assert_eq!("0e0", format!("{:e}", RocDec(0)));
+        assert_eq!("1e-18", format!("{:e}", RocDec(1)));
+        assert_eq!(
+            "1.70141183460469231731687303715884105727e20",
+            format!("{:e}", RocDec::MAX)
+        );
+    }
+
+    #[test]
+    fn lower_exp_matches_f64() {
+        for string in &["1500.0", "0.001", "-0.25", "123.456", "9.5", "0.0"] {
+            let float: f64 = string.parse().unwrap();
+
+            for precision in 0..4 {
+                assert_eq!(
+                    format!("{:.*e}", precision, float),
+                    format!("{:.*e}", precision, dec(string)),
+                    "{:?} to {} places",
+                    string,
+                    precision
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lower_exp_precision() {
+        assert_eq!("1.23e3", format!("{:.2e}", dec("1234.5")));
+        assert_eq!("1.00e3", format!("{:.2e}", dec("999.9")));
+        assert_eq!("2e0", format!("{:.0e}", dec("2.5")));
+        assert_eq!("1.500e-15", format!("{:.3e}", dec("0.0000000000000015")));
+        assert_eq!("0.00e0", format!("{:.2e}", RocDec(0)));
+        assert_eq!("-1.7e20", format!("{:.1e}", RocDec::MIN));
+    }
+
+    #[test]
+    fn exp_flags() {
+        assert_eq!("1.5E3", format!("{:E}", dec("1500.0")));
+        assert_eq!("+1.5e3", format!("{:+e}", dec("1500.0")));
+        assert_eq!("   -1.5e3", format!("{:>9e}", dec("-1500.0")));
+    }
+
+    #[test]
+    fn to_engineering_string() {
+        assert_eq!(
+            "1e-15",
+            dec("0.000000000000001").to_engineering_string(None)
+        );
+        assert_eq!("120e-6", dec("0.00012").to_engineering_string(None));
+        assert_eq!("1.2345e3", dec("1234.5").to_engineering_string(None));
+        assert_eq!("12.345e3", dec("12345.0").to_engineering_string(None));
+        assert_eq!("-500e0", dec("-500.0").to_engineering_string(None));
+        assert_eq!("0e0", RocDec(0).to_engineering_string(None));
+        assert_eq!("1e-18", RocDec(1).to_engineering_string(None));
+    }
+
+    #[test]
+    fn to_engineering_string_significant_digits() {
+        assert_eq!("12e3", dec("12345.0").to_engineering_string(Some(2)));
+        assert_eq!("10e3", dec("12345.0").to_engineering_string(Some(1)));
+        assert_eq!("12.34e3", dec("12345.0").to_engineering_string(Some(4)));
+        assert_eq!("12.36e3", dec("12355.0").to_engineering_string(Some(4)));
+        assert_eq!("1.00e3", dec("999.9").to_engineering_string(Some(3)));
+        assert_eq!(
+            "12.50e-9",
+            dec("0.0000000125").to_engineering_string(Some(4))
+        );
+        assert_eq!("100e-6", dec("0.00012").to_engineering_string(Some(1)));
+        assert_eq!("0.00e0", RocDec(0).to_engineering_string(Some(3)));
+        assert_eq!("2e0", dec("1.5").to_engineering_string(Some(0)));
+    }
+
+    #[test]
+    fn into_string() {
+        assert_eq!("-0.5", String::from(dec("-0.5")));
+    }
+
+    #[test]
+    fn write_str() {
+        let mut string = String::new();
+
+        write!(string, "[{:>8}]", dec("-1.5")).unwrap();
+
+        assert_eq!("[    -1.5]", string);
+    }
+}
@@ -1,88 +1,139 @@
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+use std::cmp::Ordering;
+
+mod accumulator;
+mod align;
+pub mod amortization;
+mod amount;
+mod convert;
+mod format;
+mod locale;
+mod parse;
+mod ratio;
+pub mod rolling;
+pub mod stats;
+mod words;
+
+pub use accumulator::RocDecAccumulator;
+pub use align::{align_decimals, AlignOptions};
+pub use amount::Amount;
+pub use convert::ConversionError;
+pub use format::DecStr;
+pub use locale::{DigitSet, FormatOptions, Grouping, Locale, NegativeStyle};
+pub use parse::{ParseErrorKind, ParseOptions, ParseRocDecError};
+pub use words::{FractionStyle, WordsOptions};
+
+/// The value times 10^DECIMAL_PLACES, stored as an i128.
+///
+/// This is `#[repr(transparent)]`, so a RocDec has exactly the same size,
+/// alignment and bits as that i128, which is also how Roc's own Dec type is
+/// laid out. It's safe to transmute between the two, or to store RocDecs
+/// in memory shared with Roc host code.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
 pub struct RocDec(i128);
 
-pub fn fuzz_new(num: i128) -> RocDec {
-    RocDec(num)
-}
-
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+// Field order matters here: the derived Ord compares hi before lo,
+// which is what makes it an unsigned 256-bit comparison.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 struct U256 {
     hi: u128,
     lo: u128,
 }
 
-// The result of calling to_string() on RocDec::MIN.
-// This is the longest to_string().
-static MIN_STR: &str = "-170141183460469231731.687303715884105728";
+/// These treat the U256 as either unsigned or two's complement, depending on
+/// the caller. Like the i128 methods they're named after, the wrapping ones
+/// give the same bits either way.
+impl U256 {
+    const ZERO: Self = U256 { hi: 0, lo: 0 };
 
-// The result of calling to_string() on RocDec::MAX.
-static MAX_STR: &str = "170141183460469231731.687303715884105727";
+    /// Sign-extend an i128 into a two's complement U256.
+    #[inline(always)]
+    fn from_i128(num: i128) -> Self {
+        U256 {
+            hi: if num.is_negative() { u128::MAX } else { 0 },
+            lo: num as u128,
+        }
+    }
 
-impl Into<String> for RocDec {
-    fn into(self) -> String {
-        return self.to_string();
+    #[inline(always)]
+    fn is_negative(self) -> bool {
+        (self.hi as i128).is_negative()
     }
-}
 
-impl<'a> std::convert::TryFrom<&'a str> for RocDec {
-    type Error = ();
+    #[inline(always)]
+    fn overflowing_add(self, other: Self) -> (Self, bool) {
+        let (lo, carry) = self.lo.overflowing_add(other.lo);
+        let (hi, overflowed1) = self.hi.overflowing_add(other.hi);
+        let (hi, overflowed2) = hi.overflowing_add(carry as u128);
 
-    fn try_from(value: &'a str) -> Result<Self, ()> {
-        // Split the string into the parts before and after the "."
-        let mut parts = value.split(".");
+        (U256 { hi, lo }, overflowed1 || overflowed2)
+    }
 
-        let before_point = match parts.next() {
-            Some(answer) => answer,
-            None => {
-                return Err(());
-            }
-        };
+    /// Add two two's complement U256s, returning None on signed overflow.
+    #[inline(always)]
+    fn checked_add_signed(self, other: Self) -> Option<Self> {
+        let sum = self.wrapping_add(other);
 
-        let after_point = match parts.next() {
-            Some(answer) if answer.len() <= Self::DECIMAL_PLACES as usize => answer,
-            _ => {
-                return Err(());
-            }
-        };
+        // Signed addition overflowed if both operands had the same sign,
+        // and the sum has the other sign.
+        if self.is_negative() == other.is_negative() && sum.is_negative() != self.is_negative() {
+            None
+        } else {
+            Some(sum)
+        }
+    }
+
+    #[inline(always)]
+    fn wrapping_add(self, other: Self) -> Self {
+        self.overflowing_add(other).0
+    }
 
-        // There should have only been one "." in the string!
-        if parts.next().is_some() {
-            return Err(());
+    #[inline(always)]
+    fn wrapping_neg(self) -> Self {
+        U256 {
+            hi: !self.hi,
+            lo: !self.lo,
         }
+        .wrapping_add(U256 { hi: 0, lo: 1 })
+    }
 
-        // Calculate the low digits - the ones after the decimal point.
-        let lo = match after_point.parse::<i128>() {
-            Ok(answer) => {
-                // Translate e.g. the 1 from 0.1 into 10000000000000000000
-                // by "restoring" the elided trailing zeroes to the number!
-                let trailing_zeroes = Self::DECIMAL_PLACES as usize - after_point.len();
-                let lo = answer * 10i128.pow(trailing_zeroes as u32);
+    #[inline(always)]
+    fn wrapping_sub(self, other: Self) -> Self {
+        self.wrapping_add(other.wrapping_neg())
+    }
 
-                if !before_point.starts_with("-") {
-                    lo
-                } else {
-                    -lo
-                }
-            }
-            Err(_) => {
-                return Err(());
-            }
-        };
+    /// Convert a two's complement U256 back into an i128, if it fits.
+    #[inline(always)]
+    fn checked_to_i128(self) -> Option<i128> {
+        let lo = self.lo as i128;
 
-        // Calculate the high digits - the ones before the decimal point.
-        match before_point.parse::<i128>() {
-            Ok(answer) => match answer.checked_mul(10i128.pow(Self::DECIMAL_PLACES)) {
-                Some(hi) => match hi.checked_add(lo) {
-                    Some(num) => Ok(RocDec(num)),
-                    None => Err(()),
-                },
-                None => Err(()),
-            },
-            Err(_) => Err(()),
+        if (self.hi == 0 && !lo.is_negative()) || (self.hi == u128::MAX && lo.is_negative()) {
+            Some(lo)
+        } else {
+            None
+        }
+    }
+
+    /// The magnitude of a two's complement U256, along with whether it was negative.
+    #[inline(always)]
+    fn unsigned_abs(self) -> (Self, bool) {
+        if self.is_negative() {
+            (self.wrapping_neg(), true)
+        } else {
+            (self, false)
         }
     }
 }
 
+// The result of calling to_string() on RocDec::MIN.
+// This is the longest to_string().
+#[cfg(test)]
+static MIN_STR: &str = "-170141183460469231731.687303715884105728";
+
+// The result of calling to_string() on RocDec::MAX.
+#[cfg(test)]
+static MAX_STR: &str = "170141183460469231731.687303715884105727";
+
 impl std::ops::Neg for RocDec {
     type Output = Self;
 
@@ -297,125 +348,167 @@ impl RocDec {
 
     const ONE_POINT_ZERO: i128 = 10i128.pow(Self::DECIMAL_PLACES);
 
-    pub fn to_string(self) -> String {
-        let self_i128 = self.0;
-        let is_negative = self_i128.is_negative();
+    /// The RocDec whose underlying i128 is `raw`, i.e. raw * 10^-18. For
+    /// example, `from_raw(1)` is the smallest positive RocDec.
+    #[inline(always)]
+    pub const fn from_raw(raw: i128) -> Self {
+        RocDec(raw)
+    }
 
-        match self_i128.checked_abs() {
-            Some(answer) => {
-                if answer == 0 {
-                    return "0.0".to_string();
-                }
+    /// The underlying i128, i.e. this value times 10^18.
+    #[inline(always)]
+    pub const fn to_raw(self) -> i128 {
+        self.0
+    }
 
-                let mut self_u128 = answer as u128;
+    /// The underlying i128 (this value times 10^18) as bytes, in
+    /// little-endian order.
+    #[inline(always)]
+    pub const fn to_le_bytes(self) -> [u8; 16] {
+        self.0.to_le_bytes()
+    }
 
-                // MIN_STR has the longest possible length of one of these.
-                // This ensures we will not need to reallocate.
-                //
-                // TODO do this with a RocStr, and don't allocate on the heap
-                // until we've run out of stack bytes for a small str.
-                let mut string = String::with_capacity(MIN_STR.len());
-                let mut display_zeroes = false;
-                let mut decimal_places_used = 0;
-
-                // The number has at least one nonzero digit before the
-                // decimal point, so we don't need to pad the rest with 0s.
-                while self_u128 != 0 {
-                    // Get the last digit of the number and convert it to ASCII.
-                    let rem = (self_u128 % 10) as u8;
-                    let ascii_byte = rem + if rem > 9 { b'a' - 10 } else { b'0' };
-
-                    // Only print zeroes if display_zeroes is set,
-                    // but always print non-zeroes.
-                    if display_zeroes || ascii_byte != b'0' {
-                        string.push(ascii_byte as char);
-
-                        // As soon as we hit our first nonzero number,
-                        // we should start showing zeroes from then on.
-                        // (All the others are potentially trailing zeroes.)
-                        display_zeroes = true;
-                    }
-
-                    self_u128 = self_u128 / 10;
-
-                    // Increment this even if we didn't push anything onto the string!
-                    // Later on, this tells us how many zeroes to use for padding.
-                    decimal_places_used += 1;
-
-                    // If we've used all our decimal places, it's now time to
-                    // add the dot.
-                    if decimal_places_used == Self::DECIMAL_PLACES {
-                        // If we're about to end the loop, push the decimal
-                        // point folloewd by the 0 we need, and break early.
-                        if self_u128 == 0 {
-                            string.push_str(".0");
-                            break;
-                        }
-
-                        // display_zeroes would only be false here if we had
-                        // seen only (trailing, unnecessary) zeroes so far.
-                        if !display_zeroes {
-                            // Since we had all zeroes after the decimal point
-                            // put a single 0 in there so the final string will
-                            // end in ".0" instead of in "."
-                            // (after reversing, of course).
-                            string.push('0');
-                        }
-
-                        string.push('.');
-
-                        // After the dot, display all zeroes.
-                        // (Otherwise we would incorrectly display `100` as `1`)
-                        display_zeroes = true;
-                    }
-                }
+    /// The underlying i128 (this value times 10^18) as bytes, in big-endian
+    /// order.
+    #[inline(always)]
+    pub const fn to_be_bytes(self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
 
-                if decimal_places_used < Self::DECIMAL_PLACES {
-                    // We ended without having emitted a dot, so print out
-                    // some zeroes as padding and then add a dot and a zero.
-                    // (Remember, these will get reversed! This adds "0." to
-                    // the beginning of the string, and then a bunch of zeroes
-                    // after the dot.)
-                    for _ in 0..(Self::DECIMAL_PLACES - decimal_places_used) {
-                        string.push('0');
-                    }
-
-                    string.push_str(".0");
-                }
+    /// The RocDec whose underlying i128 (its value times 10^18) has these
+    /// bytes, in little-endian order. This undoes `to_le_bytes`.
+    #[inline(always)]
+    pub const fn from_le_bytes(bytes: [u8; 16]) -> Self {
+        RocDec(i128::from_le_bytes(bytes))
+    }
 
-                // If number is negative, append '-'
-                if is_negative {
-                    string.push('-');
-                }
+    /// The RocDec whose underlying i128 (its value times 10^18) has these
+    /// bytes, in big-endian order. This undoes `to_be_bytes`.
+    #[inline(always)]
+    pub const fn from_be_bytes(bytes: [u8; 16]) -> Self {
+        RocDec(i128::from_be_bytes(bytes))
+    }
 
-                // Reverse the string's bytes in place. We can do this byte-wise
-                // because we know for sure they are all ASCII characters.
-                //
-                // Algorithm based on https://stackoverflow.com/a/65703808
-                let mut remaining = unsafe { string.as_bytes_mut() };
-
-                loop {
-                    match remaining {
-                        [] | [_] => break,
-                        [first, rest @ .., last] => {
-                            std::mem::swap(first, last);
-
-                            remaining = rest;
-                        }
-                    }
-                }
+    /// Round to the given number of decimal places using the given mode.
+    ///
+    /// Asking for DECIMAL_PLACES or more places returns the number unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if rounding away from zero goes past RocDec::MIN or
+    /// RocDec::MAX, e.g. `RocDec::MAX.round(0, RoundingMode::Up)`. Use
+    /// `checked_round` to handle that instead.
+    pub fn round(self, places: u32, mode: RoundingMode) -> Self {
+        self.checked_round(places, mode)
+            .expect("rounded value is too large or too small to fit in a RocDec")
+    }
 
-                string
+    /// Round to the given number of decimal places using the given mode,
+    /// or return None if the answer goes past RocDec::MIN or RocDec::MAX.
+    pub fn checked_round(self, places: u32, mode: RoundingMode) -> Option<Self> {
+        let is_negative = self.0.is_negative();
+        let magnitude = round_magnitude(self.0.unsigned_abs(), is_negative, places, mode);
+
+        if is_negative {
+            // i128::MIN has no positive counterpart, so go through wrapping_neg
+            // to allow a magnitude of exactly 2^127 to round-trip.
+            if magnitude <= i128::MIN.unsigned_abs() {
+                Some(RocDec((magnitude as i128).wrapping_neg()))
+            } else {
+                None
             }
-            None => {
-                // if it was exactly i128::MIN, then taking its absolute value
-                // is impossible to represent...but we also know the exact str:
-                MIN_STR.to_string()
+        } else if magnitude <= i128::MAX as u128 {
+            Some(RocDec(magnitude as i128))
+        } else {
+            None
+        }
+    }
+}
+
+/// How to round a number that has more decimal places than we want to keep.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum RoundingMode {
+    /// Round toward zero, i.e. just drop the extra digits.
+    Down,
+    /// Round away from zero.
+    Up,
+    /// Round toward negative infinity.
+    Floor,
+    /// Round toward positive infinity.
+    Ceiling,
+    /// Round to the nearest value; a tie goes away from zero.
+    HalfUp,
+    /// Round to the nearest value; a tie goes toward zero.
+    HalfDown,
+    /// Round to the nearest value; a tie goes to the even neighbor.
+    /// This is also known as banker's rounding.
+    HalfEven,
+}
+
+impl RoundingMode {
+    /// Given a magnitude which has already been truncated toward zero,
+    /// decide whether it should be bumped one unit further away from zero.
+    ///
+    /// `discarded` says how the digits we dropped compare to exactly half
+    /// of a unit, and `inexact` says whether any of them were nonzero.
+    #[inline(always)]
+    pub(crate) fn rounds_away(
+        self,
+        is_negative: bool,
+        is_odd: bool,
+        discarded: Ordering,
+        inexact: bool,
+    ) -> bool {
+        if !inexact {
+            return false;
+        }
+
+        match self {
+            RoundingMode::Down => false,
+            RoundingMode::Up => true,
+            RoundingMode::Floor => is_negative,
+            RoundingMode::Ceiling => !is_negative,
+            RoundingMode::HalfUp => discarded != Ordering::Less,
+            RoundingMode::HalfDown => discarded == Ordering::Greater,
+            RoundingMode::HalfEven => {
+                discarded == Ordering::Greater || (discarded == Ordering::Equal && is_odd)
             }
         }
     }
 }
 
+/// Round the magnitude of a RocDec's i128 to the given number of decimal places.
+///
+/// This can't overflow, because the magnitude of an i128 is at most 2^127,
+/// and rounding adds less than 10^DECIMAL_PLACES to it.
+#[inline(always)]
+pub(crate) fn round_magnitude(
+    magnitude: u128,
+    is_negative: bool,
+    places: u32,
+    mode: RoundingMode,
+) -> u128 {
+    if places >= RocDec::DECIMAL_PLACES {
+        return magnitude;
+    }
+
+    let unit = 10u128.pow(RocDec::DECIMAL_PLACES - places);
+    let truncated = magnitude / unit;
+    let rem = magnitude % unit;
+
+    // unit is always a multiple of 10, so unit / 2 is exactly half.
+    if mode.rounds_away(
+        is_negative,
+        truncated % 2 == 1,
+        rem.cmp(&(unit / 2)),
+        rem != 0,
+    ) {
+        (truncated + 1) * unit
+    } else {
+        truncated * unit
+    }
+}
+
 /// Multiply two 128-bit ints and divide the result by 10^DECIMAL_PLACES
 ///
 /// Adapted from https://github.com/nlordell/ethnum-rs
@@ -637,6 +730,20 @@ fn mul_and_decimalize(a: u128, b: u128) -> u128 {
     c >> 59 | (d << (128 - 59))
 }
 
+/// Multiply two i128s into a two's complement U256.
+///
+/// This can't overflow, since the magnitude of the product is at most 2^254.
+#[inline(always)]
+fn mul_i128(a: i128, b: i128) -> U256 {
+    let product = mul_u128(a.unsigned_abs(), b.unsigned_abs());
+
+    if a.is_negative() != b.is_negative() {
+        product.wrapping_neg()
+    } else {
+        product
+    }
+}
+
 /// Adapted from https://github.com/nlordell/ethnum-rs
 /// Copyright (c) 2020 Nicholas Rodrigues Lordello
 /// Licensed under the Apache License version 2.0
@@ -666,7 +773,7 @@ fn mul_u128(a: u128, b: u128) -> U256 {
 
 #[cfg(test)]
 mod tests {
-    use crate::RocDec;
+    use crate::{RocDec, RoundingMode};
     use std::convert::TryInto;
     use std::ops::{Add, Div, Mul, Neg, Sub};
 
@@ -1028,6 +1135,80 @@ mod tests {
         assert_div("-1.01", "-7.02", "0.143874643874643874");
     }
 
+    fn assert_round(dec: &str, places: u32, mode: RoundingMode, expected: &str) {
+        let dec: RocDec = dec.try_into().unwrap();
+
+        assert_eq!(expected, dec.round(places, mode).to_string());
+    }
+
+    #[test]
+    fn round_modes() {
+        assert_round("1.25", 1, RoundingMode::Down, "1.2");
+        assert_round("1.25", 1, RoundingMode::Up, "1.3");
+        assert_round("1.25", 1, RoundingMode::Floor, "1.2");
+        assert_round("1.25", 1, RoundingMode::Ceiling, "1.3");
+        assert_round("1.25", 1, RoundingMode::HalfUp, "1.3");
+        assert_round("1.25", 1, RoundingMode::HalfDown, "1.2");
+        assert_round("1.25", 1, RoundingMode::HalfEven, "1.2");
+        assert_round("1.35", 1, RoundingMode::HalfEven, "1.4");
+        assert_round("1.251", 1, RoundingMode::HalfDown, "1.3");
+    }
+
+    #[test]
+    fn round_negative() {
+        assert_round("-1.25", 1, RoundingMode::Down, "-1.2");
+        assert_round("-1.25", 1, RoundingMode::Up, "-1.3");
+        assert_round("-1.25", 1, RoundingMode::Floor, "-1.3");
+        assert_round("-1.25", 1, RoundingMode::Ceiling, "-1.2");
+        assert_round("-1.25", 1, RoundingMode::HalfUp, "-1.3");
+        assert_round("-1.25", 1, RoundingMode::HalfEven, "-1.2");
+    }
+
+    #[test]
+    fn round_exact() {
+        assert_round("1.5", 0, RoundingMode::Up, "2.0");
+        assert_round("2.0", 0, RoundingMode::Up, "2.0");
+        assert_round(
+            "0.000000000000000001",
+            18,
+            RoundingMode::Up,
+            "0.000000000000000001",
+        );
+        assert_round("0.000000000000000001", 17, RoundingMode::Down, "0.0");
+        assert_round(super::MIN_STR, 18, RoundingMode::Up, super::MIN_STR);
+        assert_round(
+            super::MIN_STR,
+            0,
+            RoundingMode::Down,
+            "-170141183460469231731.0",
+        );
+    }
+
+    #[test]
+    fn checked_round() {
+        assert_eq!(None, RocDec::MAX.checked_round(0, RoundingMode::Up));
+        assert_eq!(None, RocDec::MIN.checked_round(2, RoundingMode::Floor));
+        assert_eq!(
+            Some(dec("170141183460469231731.0")),
+            RocDec::MAX.checked_round(0, RoundingMode::Down)
+        );
+        assert_eq!(None, RocDec::MIN.checked_round(2, RoundingMode::HalfEven));
+        assert_eq!(
+            Some(dec("-170141183460469231731.68")),
+            RocDec::MIN.checked_round(2, RoundingMode::Ceiling)
+        );
+        assert_eq!(
+            Some(RocDec::MIN),
+            RocDec::MIN.checked_round(18, RoundingMode::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "too large or too small")]
+    fn round_overflow() {
+        RocDec::MAX.round(0, RoundingMode::Up);
+    }
+
     #[test]
     fn div_extremes() {
         assert_div("0.0", super::MIN_STR, "0.0");
@@ -1035,4 +1216,42 @@ mod tests {
         assert_div(super::MIN_STR, "1.0", super::MIN_STR);
         assert_div(super::MAX_STR, "1.0", super::MAX_STR);
     }
+
+    fn dec(string: &str) -> RocDec {
+        string.try_into().unwrap()
+    }
+
+    #[test]
+    fn raw() {
+        assert_eq!(RocDec::from_raw(1_500_000_000_000_000_000), dec("1.5"));
+        assert_eq!(-1_500_000_000_000_000_000, dec("-1.5").to_raw());
+        assert_eq!(RocDec::MIN, RocDec::from_raw(i128::MIN));
+        assert_eq!(i128::MAX, RocDec::MAX.to_raw());
+        assert_eq!("0.000000000000000001", RocDec::from_raw(1).to_string());
+    }
+
+    #[test]
+    fn bytes() {
+        let dec = dec("-1.5");
+        let raw: i128 = -1_500_000_000_000_000_000;
+
+        assert_eq!(raw.to_le_bytes(), dec.to_le_bytes());
+        assert_eq!(raw.to_be_bytes(), dec.to_be_bytes());
+        assert_eq!(dec, RocDec::from_le_bytes(dec.to_le_bytes()));
+        assert_eq!(dec, RocDec::from_be_bytes(dec.to_be_bytes()));
+
+        let mut one = [0; 16];
+        one[15] = 1;
+        assert_eq!(RocDec::from_raw(1), RocDec::from_be_bytes(one));
+        one.reverse();
+        assert_eq!(RocDec::from_raw(1), RocDec::from_le_bytes(one));
+    }
+
+    #[test]
+    fn layout() {
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(size_of::<i128>(), size_of::<RocDec>());
+        assert_eq!(align_of::<i128>(), align_of::<RocDec>());
+    }
 }
@@ -0,0 +1,393 @@
+use crate::{RocDec, RoundingMode};
+
+/// How to group the digits before the decimal point.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Grouping {
+    /// Groups of three, e.g. "12,345,678".
+    Thousands,
+    /// The last three digits, then groups of two, e.g. "1,23,45,678".
+    /// This is how lakhs and crores are written in India.
+    Indian,
+}
+
+/// How to show that a number is negative.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum NegativeStyle {
+    /// "-12.5"
+    LeadingMinus,
+    /// "(12.5)", as in accounting.
+    Parentheses,
+    /// "12.5-", as in some bank statements.
+    TrailingMinus,
+}
+
+/// Which characters to use for the digits 0 through 9.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum DigitSet {
+    /// 0123456789
+    Ascii,
+    /// ٠١٢٣٤٥٦٧٨٩, used with Arabic.
+    ArabicIndic,
+    /// ۰۱۲۳۴۵۶۷۸۹, used with Persian and Urdu.
+    ExtendedArabicIndic,
+    /// ०१२३४५६७८९, used with Hindi and Marathi.
+    Devanagari,
+    /// ০১২৩৪৫৬৭৮৯, used with Bengali.
+    Bengali,
+    /// ๐๑๒๓๔๕๖๗๘๙, used with Thai.
+    Thai,
+}
+
+impl DigitSet {
+    /// Each of these is ten consecutive code points, so the zero is enough
+    /// to find the rest.
+    fn zero(self) -> u32 {
+        match self {
+            DigitSet::Ascii => '0' as u32,
+            DigitSet::ArabicIndic => '\u{660}' as u32,
+            DigitSet::ExtendedArabicIndic => '\u{6f0}' as u32,
+            DigitSet::Devanagari => '\u{966}' as u32,
+            DigitSet::Bengali => '\u{9e6}' as u32,
+            DigitSet::Thai => '\u{e50}' as u32,
+        }
+    }
+
+    fn digit(self, ascii_digit: u8) -> char {
+        // Every zero above is followed by nine more digits, all of which
+        // are valid chars.
+        std::char::from_u32(self.zero() + (ascii_digit - b'0') as u32).unwrap_or('?')
+    }
+}
+
+/// Common conventions for writing numbers, to start a FormatOptions from.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Locale {
+    /// "1,234,567.89"
+    EnUs,
+    /// "12,34,567.89"
+    EnIn,
+    /// "1.234.567,89"
+    DeDe,
+    /// "1’234’567.89"
+    DeCh,
+    /// "1 234 567,89", with a narrow no-break space between groups.
+    FrFr,
+    /// "١٬٢٣٤٬٥٦٧٫٨٩"
+    ArEg,
+}
+
+/// Options for formatting a RocDec for people to read, such as
+/// "1.234.567,89" or "(1,234.50)".
+///
+/// The defaults give the same string as `to_string`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FormatOptions {
+    group_separator: Option<char>,
+    grouping: Grouping,
+    pub(crate) decimal_separator: char,
+    negative_style: NegativeStyle,
+    digits: DigitSet,
+    pub(crate) decimal_places: Option<(u32, RoundingMode)>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatOptions {
+    pub fn new() -> Self {
+        FormatOptions {
+            group_separator: None,
+            grouping: Grouping::Thousands,
+            decimal_separator: '.',
+            negative_style: NegativeStyle::LeadingMinus,
+            digits: DigitSet::Ascii,
+            decimal_places: None,
+        }
+    }
+
+    /// The separators, grouping and digits that the locale uses. Any of
+    /// them can be changed afterwards.
+    pub fn for_locale(locale: Locale) -> Self {
+        let options = FormatOptions::new();
+
+        match locale {
+            Locale::EnUs => options.group_separator(','),
+            Locale::EnIn => options.group_separator(',').grouping(Grouping::Indian),
+            Locale::DeDe => options.group_separator('.').decimal_separator(','),
+            Locale::DeCh => options.group_separator('\u{2019}'),
+            Locale::FrFr => options.group_separator('\u{202f}').decimal_separator(','),
+            Locale::ArEg => options
+                .group_separator('\u{66c}')
+                .decimal_separator('\u{66b}')
+                .digits(DigitSet::ArabicIndic),
+        }
+    }
+
+    /// Put this character between groups of digits before the decimal point.
+    pub fn group_separator(mut self, separator: char) -> Self {
+        self.group_separator = Some(separator);
+
+        self
+    }
+
+    /// How big the groups are. This does nothing without a group separator.
+    pub fn grouping(mut self, grouping: Grouping) -> Self {
+        self.grouping = grouping;
+
+        self
+    }
+
+    /// Use this character instead of '.' as the decimal point.
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+
+        self
+    }
+
+    pub fn negative_style(mut self, style: NegativeStyle) -> Self {
+        self.negative_style = style;
+
+        self
+    }
+
+    pub fn digits(mut self, digits: DigitSet) -> Self {
+        self.digits = digits;
+
+        self
+    }
+
+    /// Always show exactly this many digits after the decimal point,
+    /// rounding with the given mode if there are more. With 0, there's no
+    /// decimal point either.
+    pub fn decimal_places(mut self, places: u32, mode: RoundingMode) -> Self {
+        self.decimal_places = Some((places, mode));
+
+        self
+    }
+}
+
+impl RocDec {
+    /// Format using the given options, e.g. to group digits or use a
+    /// locale's decimal separator.
+    pub fn to_string_with(&self, options: &FormatOptions) -> String {
+        let (is_negative, unsigned) = match options.decimal_places {
+            Some((places, mode)) => self.fixed_places(places as usize, mode),
+            None => {
+                let mut buf = [0; Self::MAX_STR_LEN];
+                let string = self.to_str_buf(&mut buf);
+
+                match string.strip_prefix('-') {
+                    Some(digits) => (true, digits.to_string()),
+                    None => (false, string.to_string()),
+                }
+            }
+        };
+
+        let (int_digits, frac_digits) = match unsigned.find('.') {
+            Some(point) => (&unsigned[..point], &unsigned[point + 1..]),
+            None => (unsigned.as_str(), ""),
+        };
+
+        let mut string = String::with_capacity(unsigned.len() * 2 + 2);
+
+        if is_negative {
+            match options.negative_style {
+                NegativeStyle::LeadingMinus => string.push('-'),
+                NegativeStyle::Parentheses => string.push('('),
+                NegativeStyle::TrailingMinus => {}
+            }
+        }
+
+        for (index, byte) in int_digits.bytes().enumerate() {
+            let remaining = int_digits.len() - index;
+
+            if index > 0 && is_group_start(remaining, options.grouping) {
+                if let Some(separator) = options.group_separator {
+                    string.push(separator);
+                }
+            }
+
+            string.push(options.digits.digit(byte));
+        }
+
+        if !frac_digits.is_empty() {
+            string.push(options.decimal_separator);
+            string.extend(frac_digits.bytes().map(|byte| options.digits.digit(byte)));
+        }
+
+        if is_negative {
+            match options.negative_style {
+                NegativeStyle::LeadingMinus => {}
+                NegativeStyle::Parentheses => string.push(')'),
+                NegativeStyle::TrailingMinus => string.push('-'),
+            }
+        }
+
+        string
+    }
+}
+
+/// Whether a new group starts at a digit which has `remaining` digits from
+/// it to the decimal point (including itself).
+fn is_group_start(remaining: usize, grouping: Grouping) -> bool {
+    match grouping {
+        Grouping::Thousands => remaining % 3 == 0,
+        Grouping::Indian => remaining >= 3 && (remaining - 3) % 2 == 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DigitSet, FormatOptions, Grouping, Locale, NegativeStyle};
+    use crate::{RocDec, RoundingMode};
+    use std::convert::TryInto;
+
+    fn dec(string: &str) -> RocDec {
+        string.try_into().unwrap()
+    }
+
+    fn assert_format(string: &str, options: &FormatOptions, expected: &str) {
+        assert_eq!(
+            expected,
+            dec(string).to_string_with(options),
+            "{:?}",
+            string
+        );
+    }
+
+    #[test]
+    fn defaults() {
+        let options = FormatOptions::new();
+
+        for string in &["0.0", "1.5", "-1234567.25", crate::MIN_STR, crate::MAX_STR] {
+            assert_format(string, &options, string);
+        }
+    }
+
+    #[test]
+    fn thousands() {
+        let options = FormatOptions::new().group_separator(',');
+
+        assert_format("1.5", &options, "1.5");
+        assert_format("999.0", &options, "999.0");
+        assert_format("1000.0", &options, "1,000.0");
+        assert_format("-1234567.891", &options, "-1,234,567.891");
+        assert_format("123456.0", &options, "123,456.0");
+        assert_format(
+            crate::MAX_STR,
+            &options,
+            "170,141,183,460,469,231,731.687303715884105727",
+        );
+    }
+
+    #[test]
+    fn indian() {
+        let options = FormatOptions::for_locale(Locale::EnIn);
+
+        assert_format("100.0", &options, "100.0");
+        assert_format("1000.0", &options, "1,000.0");
+        assert_format("100000.0", &options, "1,00,000.0");
+        assert_format("12345678.9", &options, "1,23,45,678.9");
+        assert_format("-1234567.0", &options, "-12,34,567.0");
+    }
+
+    #[test]
+    fn locales() {
+        assert_format(
+            "1234567.89",
+            &FormatOptions::for_locale(Locale::EnUs),
+            "1,234,567.89",
+        );
+        assert_format(
+            "1234567.89",
+            &FormatOptions::for_locale(Locale::DeDe),
+            "1.234.567,89",
+        );
+        assert_format(
+            "1234567.89",
+            &FormatOptions::for_locale(Locale::DeCh),
+            "1\u{2019}234\u{2019}567.89",
+        );
+        assert_format(
+            "1234567.89",
+            &FormatOptions::for_locale(Locale::FrFr),
+            "1\u{202f}234\u{202f}567,89",
+        );
+        assert_format(
+            "1234567.89",
+            &FormatOptions::for_locale(Locale::ArEg),
+            "١٬٢٣٤٬٥٦٧٫٨٩",
+        );
+    }
+
+    #[test]
+    fn negative_styles() {
+        let options = FormatOptions::new().group_separator(',');
+
+        assert_format(
+            "-1234.5",
+            &options.negative_style(NegativeStyle::Parentheses),
+            "(1,234.5)",
+        );
+        assert_format(
+            "-1234.5",
+            &options.negative_style(NegativeStyle::TrailingMinus),
+            "1,234.5-",
+        );
+        assert_format(
+            "1234.5",
+            &options.negative_style(NegativeStyle::Parentheses),
+            "1,234.5",
+        );
+    }
+
+    #[test]
+    fn digit_sets() {
+        let options = FormatOptions::new().group_separator(',');
+
+        assert_format(
+            "1234567890.5",
+            &options.digits(DigitSet::Devanagari),
+            "१,२३४,५६७,८९०.५",
+        );
+        assert_format(
+            "1234567890.5",
+            &options.digits(DigitSet::ExtendedArabicIndic),
+            "۱,۲۳۴,۵۶۷,۸۹۰.۵",
+        );
+        assert_format(
+            "1234567890.5",
+            &options.digits(DigitSet::Bengali),
+            "১,২৩৪,৫৬৭,৮৯০.৫",
+        );
+        assert_format(
+            "1234567890.5",
+            &options.digits(DigitSet::Thai),
+            "๑,๒๓๔,๕๖๗,๘๙๐.๕",
+        );
+    }
+
+    #[test]
+    fn decimal_places() {
+        let options = FormatOptions::for_locale(Locale::DeDe)
+            .decimal_places(2, RoundingMode::HalfUp)
+            .negative_style(NegativeStyle::Parentheses);
+
+        assert_format("1234.5", &options, "1.234,50");
+        assert_format("-1234.565", &options, "(1.234,57)");
+        assert_format("-0.001", &options, "0,00");
+        assert_format(
+            "999999.999",
+            &options.grouping(Grouping::Indian),
+            "10.00.000,00",
+        );
+        assert_format(
+            "1234.5",
+            &FormatOptions::new().decimal_places(0, RoundingMode::HalfEven),
+            "1234",
+        );
+    }
+}
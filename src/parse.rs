@@ -0,0 +1,1282 @@
+use crate::{RocDec, RoundingMode};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// The reason a string could not be parsed into a RocDec.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ParseErrorKind {
+    /// The string was empty.
+    Empty,
+    /// There was a character which doesn't belong in a decimal number.
+    InvalidDigit,
+    /// The number had nonzero digits smaller than 10^-DECIMAL_PLACES,
+    /// which a RocDec can't represent.
+    TooManyDecimalPlaces,
+    /// The number was too big or too small to fit in a RocDec.
+    Overflow,
+    /// The ParseOptions used the same character as both the group
+    /// separator and the decimal separator, so there's no telling them apart.
+    SameSeparators,
+}
+
+/// An error from parsing a RocDec, along with the byte offset into the
+/// string where the problem was found.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ParseRocDecError {
+    kind: ParseErrorKind,
+    offset: usize,
+}
+
+impl ParseRocDecError {
+    pub(crate) fn new(kind: ParseErrorKind, offset: usize) -> Self {
+        ParseRocDecError { kind, offset }
+    }
+
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// The byte offset into the string where the problem was found.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for ParseRocDecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            ParseErrorKind::Empty => "cannot parse a decimal from an empty string",
+            ParseErrorKind::InvalidDigit => "invalid digit found in decimal",
+            ParseErrorKind::TooManyDecimalPlaces => "decimal has too many decimal places",
+            ParseErrorKind::Overflow => "decimal is too large or too small to fit in a RocDec",
+            ParseErrorKind::SameSeparators => "group and decimal separators are the same",
+        };
+
+        write!(f, "{} at byte {}", description, self.offset)
+    }
+}
+
+impl std::error::Error for ParseRocDecError {}
+
+/// Options for parsing strings which aren't plain decimal numbers, such as
+/// "1.234.567,89" or "(1'234.50)".
+///
+/// The defaults parse the same strings as `str::parse`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ParseOptions {
+    group_separator: Option<char>,
+    decimal_separator: char,
+    trim_whitespace: bool,
+    allow_parentheses: bool,
+    loose_grouping: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        ParseOptions {
+            group_separator: None,
+            decimal_separator: '.',
+            trim_whitespace: false,
+            allow_parentheses: false,
+            loose_grouping: false,
+        }
+    }
+
+    /// Allow this character between digits before the decimal point, e.g.
+    /// '_', ',', '.', ' ' or '\''. The first group must have one to three
+    /// digits and every group after it exactly three (see `loose_grouping`),
+    /// so with '.' as the group separator, "1.234" is 1234 but "1.5" and
+    /// "1234.567" are errors.
+    ///
+    /// It's an error to parse with the same group and decimal separators.
+    ///
+    /// Panics if the separator is a digit, a sign, or an exponent marker.
+    pub fn group_separator(mut self, separator: char) -> Self {
+        assert_valid_separator(separator);
+
+        self.group_separator = Some(separator);
+
+        self
+    }
+
+    /// Use this character instead of '.' as the decimal point, e.g. ','.
+    ///
+    /// Panics if the separator is a digit, a sign, or an exponent marker.
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        assert_valid_separator(separator);
+
+        self.decimal_separator = separator;
+
+        self
+    }
+
+    /// Allow groups of any size between group separators, e.g. for Indian
+    /// grouping like "12,34,567" or for "1_0000_0000". Each separator still
+    /// needs a digit on either side of it.
+    pub fn loose_grouping(mut self, loose: bool) -> Self {
+        self.loose_grouping = loose;
+
+        self
+    }
+
+    /// Ignore ASCII whitespace at the start and end of the string.
+    pub fn trim_whitespace(mut self, trim: bool) -> Self {
+        self.trim_whitespace = trim;
+
+        self
+    }
+
+    /// Treat a number wrapped in parentheses, e.g. "(45.00)", as negative.
+    /// A number in parentheses can't also have a sign.
+    pub fn allow_parentheses(mut self, allow: bool) -> Self {
+        self.allow_parentheses = allow;
+
+        self
+    }
+}
+
+fn assert_valid_separator(separator: char) {
+    assert!(
+        !matches!(separator, '0'..='9' | '+' | '-' | 'e' | 'E'),
+        "{:?} can't be used as a separator, because it's part of a number",
+        separator
+    );
+}
+
+impl RocDec {
+    /// Parse a string using the given options, e.g. to allow digit group
+    /// separators or a decimal comma.
+    pub fn parse_with(value: &str, options: &ParseOptions) -> Result<Self, ParseRocDecError> {
+        let number = scan(value.as_bytes(), options)?;
+
+        number.to_roc_dec()
+    }
+}
+
+impl RocDec {
+    /// Parse a decimal number from ASCII bytes, accepting the same forms as
+    /// `str::parse`. Since this doesn't need a `&str`, it skips UTF-8
+    /// validation, which is useful when parsing numbers straight out of a
+    /// file or network buffer. Any non-ASCII byte is an invalid digit.
+    pub fn parse_bytes(bytes: &[u8]) -> Result<Self, ParseRocDecError> {
+        let number = scan(bytes, &ParseOptions::new())?;
+
+        number.to_roc_dec()
+    }
+
+    /// Parse a string, rounding it to DECIMAL_PLACES decimal places if it
+    /// has more than that, rather than returning an error.
+    ///
+    /// Also returns whether any rounding happened, i.e. whether any nonzero
+    /// digits had to be dropped.
+    pub fn parse_rounded(
+        value: &str,
+        mode: RoundingMode,
+    ) -> Result<(Self, bool), ParseRocDecError> {
+        let number = scan(value.as_bytes(), &ParseOptions::new())?;
+
+        number.to_roc_dec_rounded(Some(mode))
+    }
+}
+
+impl RocDec {
+    /// Parse a decimal literal in a const context, e.g. for `const` and
+    /// `static` items. The `dec!` macro wraps this, and is usually nicer.
+    ///
+    /// This accepts the same strings as `str::parse`, except for scientific
+    /// notation. It panics if the string isn't a valid RocDec, which in a
+    /// const context is a compile error.
+    pub const fn from_str_const(value: &str) -> Self {
+        let bytes = value.as_bytes();
+
+        if bytes.is_empty() {
+            panic!("cannot parse a decimal from an empty string");
+        }
+
+        let (is_negative, mut index) = match bytes[0] {
+            b'-' => (true, 1),
+            b'+' => (false, 1),
+            _ => (false, 0),
+        };
+
+        let mut int_part: u128 = 0;
+        let mut frac_part: u128 = 0;
+        let mut frac_digit_count = 0;
+        let mut has_digits = false;
+        let mut has_point = false;
+
+        while index < bytes.len() {
+            let byte = bytes[index];
+
+            match byte {
+                b'0'..=b'9' => {
+                    let digit = (byte - b'0') as u128;
+
+                    has_digits = true;
+
+                    if !has_point {
+                        int_part = const_mul_add(int_part, 10, digit);
+                    } else if frac_digit_count < Self::DECIMAL_PLACES {
+                        frac_part = frac_part * 10 + digit;
+                        frac_digit_count += 1;
+                    } else if digit != 0 {
+                        panic!("decimal has too many decimal places");
+                    }
+                }
+                b'.' if !has_point => has_point = true,
+                _ => panic!("invalid digit found in decimal"),
+            }
+
+            index += 1;
+        }
+
+        if !has_digits {
+            panic!("invalid digit found in decimal");
+        }
+
+        let frac_part =
+            frac_part * POWERS_OF_TEN[(Self::DECIMAL_PLACES - frac_digit_count) as usize];
+        let magnitude = const_mul_add(int_part, Self::ONE_POINT_ZERO as u128, frac_part);
+
+        // i128::MIN's magnitude is one more than i128::MAX.
+        if magnitude > i128::MAX as u128 + is_negative as u128 {
+            panic!("decimal is too large or too small to fit in a RocDec");
+        }
+
+        if is_negative {
+            RocDec((magnitude as i128).wrapping_neg())
+        } else {
+            RocDec(magnitude as i128)
+        }
+    }
+}
+
+/// answer * multiplier + addend, panicking on overflow. This is for const
+/// fns, which can't use `?` or `ok_or`.
+const fn const_mul_add(answer: u128, multiplier: u128, addend: u128) -> u128 {
+    if let Some(product) = answer.checked_mul(multiplier) {
+        if let Some(sum) = product.checked_add(addend) {
+            return sum;
+        }
+    }
+
+    panic!("decimal is too large or too small to fit in a RocDec");
+}
+
+/// A RocDec literal, parsed at compile time, e.g. `dec!("0.0025")`.
+///
+/// An invalid or out-of-range literal is a compile error rather than a
+/// runtime panic, and the result can be used in `const` and `static` items.
+#[macro_export]
+macro_rules! dec {
+    ($value:expr) => {{
+        const VALUE: $crate::RocDec = $crate::RocDec::from_str_const($value);
+
+        VALUE
+    }};
+}
+
+impl FromStr for RocDec {
+    type Err = ParseRocDecError;
+
+    fn from_str(value: &str) -> Result<Self, ParseRocDecError> {
+        Self::parse_with(value, &ParseOptions::new())
+    }
+}
+
+impl<'a> std::convert::TryFrom<&'a str> for RocDec {
+    type Error = ParseRocDecError;
+
+    fn try_from(value: &'a str) -> Result<Self, ParseRocDecError> {
+        value.parse()
+    }
+}
+
+/// A syntactically valid number, split into its parts but not yet
+/// converted into a RocDec.
+pub(crate) struct Number<'a> {
+    pub(crate) is_negative: bool,
+    /// The digits before the decimal point. These may have group separators
+    /// between them, so any byte which isn't a digit should be skipped.
+    int_digits: &'a [u8],
+    /// How many digits (not counting separators) are in int_digits.
+    int_digit_count: usize,
+    /// The byte offset of int_digits in the original string.
+    int_offset: usize,
+    /// The digits after the decimal point.
+    frac_digits: &'a [u8],
+    /// The byte offset of frac_digits in the original string.
+    frac_offset: usize,
+    /// The number after the "e", if any. This saturates rather than
+    /// overflowing, since anything that large is out of range anyway.
+    pub(crate) exponent: i64,
+}
+
+/// Check that the bytes are a valid decimal number, and split them up.
+///
+/// The accepted forms are an optional sign followed by digits with at most
+/// one "." anywhere among them, e.g. "5", "+1.5", "-.5" or "5." - as long as
+/// there is at least one digit. That can be followed by an exponent, which
+/// is an "e" or "E", an optional sign, and at least one digit, e.g. "1.5e-3".
+/// Anything else, including whitespace, is an error.
+///
+/// The options can relax this; see ParseOptions.
+pub(crate) fn scan<'a>(
+    bytes: &'a [u8],
+    options: &ParseOptions,
+) -> Result<Number<'a>, ParseRocDecError> {
+    use ParseErrorKind::*;
+
+    let mut start = 0;
+    let mut end = bytes.len();
+
+    if options.trim_whitespace {
+        while start < end && bytes[start].is_ascii_whitespace() {
+            start += 1;
+        }
+
+        while end > start && bytes[end - 1].is_ascii_whitespace() {
+            end -= 1;
+        }
+    }
+
+    if start == end {
+        return Err(ParseRocDecError::new(Empty, start));
+    }
+
+    if options.group_separator == Some(options.decimal_separator) {
+        return Err(ParseRocDecError::new(SameSeparators, start));
+    }
+
+    let is_parenthesized = options.allow_parentheses && bytes[start] == b'(';
+
+    if is_parenthesized {
+        if end - start < 2 || bytes[end - 1] != b')' {
+            return Err(ParseRocDecError::new(InvalidDigit, end));
+        }
+
+        start += 1;
+        end -= 1;
+    }
+
+    // From here on, offsets are still relative to the original string,
+    // but we stop at the end of the (trimmed, unparenthesized) number.
+    let bytes = &bytes[..end];
+
+    let mut group_separator = [0; 4];
+    let group_separator = options
+        .group_separator
+        .map(|separator| separator.encode_utf8(&mut group_separator).as_bytes());
+
+    let mut decimal_separator = [0; 4];
+    let decimal_separator = options
+        .decimal_separator
+        .encode_utf8(&mut decimal_separator)
+        .as_bytes();
+
+    let (is_negative, int_start) = if is_parenthesized {
+        (true, start)
+    } else {
+        parse_sign(bytes, start)
+    };
+
+    let (int_end, int_digit_count) =
+        skip_grouped_digits(bytes, int_start, group_separator, options.loose_grouping)?;
+
+    let (frac_start, frac_end) = if bytes[int_end..].starts_with(decimal_separator) {
+        let frac_start = int_end + decimal_separator.len();
+
+        (frac_start, skip_digits(bytes, frac_start))
+    } else {
+        (int_end, int_end)
+    };
+
+    // A sign or a "." on its own isn't a number.
+    if int_digit_count == 0 && frac_start == frac_end {
+        return Err(ParseRocDecError::new(InvalidDigit, frac_end));
+    }
+
+    let (exponent, end) = match bytes.get(frac_end) {
+        Some(b'e') | Some(b'E') => {
+            let (is_exponent_negative, exponent_start) = parse_sign(bytes, frac_end + 1);
+            let exponent_end = skip_digits(bytes, exponent_start);
+
+            if exponent_start == exponent_end {
+                return Err(ParseRocDecError::new(InvalidDigit, exponent_end));
+            }
+
+            let exponent = bytes[exponent_start..exponent_end]
+                .iter()
+                .fold(0i64, |answer, byte| {
+                    answer
+                        .saturating_mul(10)
+                        .saturating_add((byte - b'0') as i64)
+                });
+
+            if is_exponent_negative {
+                (-exponent, exponent_end)
+            } else {
+                (exponent, exponent_end)
+            }
+        }
+        _ => (0, frac_end),
+    };
+
+    // Whatever stopped us, it wasn't a digit, a separator, or an exponent.
+    if end < bytes.len() {
+        return Err(ParseRocDecError::new(InvalidDigit, end));
+    }
+
+    Ok(Number {
+        is_negative,
+        int_digits: &bytes[int_start..int_end],
+        int_digit_count,
+        int_offset: int_start,
+        frac_digits: &bytes[frac_start..frac_end],
+        frac_offset: frac_start,
+        exponent,
+    })
+}
+
+impl<'a> Number<'a> {
+    /// Convert to a RocDec, returning an error if that would lose any
+    /// nonzero digits.
+    pub(crate) fn to_roc_dec(&self) -> Result<RocDec, ParseRocDecError> {
+        self.to_roc_dec_rounded(None).map(|(answer, _)| answer)
+    }
+
+    /// Convert to a RocDec, rounding away any digits smaller than the
+    /// smallest value we can represent. (If no rounding mode is given, it's
+    /// an error if any of those digits are nonzero.)
+    ///
+    /// Also returns whether any nonzero digits were rounded away.
+    fn to_roc_dec_rounded(
+        &self,
+        rounding: Option<RoundingMode>,
+    ) -> Result<(RocDec, bool), ParseRocDecError> {
+        let (magnitude, is_inexact) = self.magnitude(rounding)?;
+
+        // i128::MIN has no positive counterpart, so negative numbers get one
+        // more value of range than positive ones.
+        let answer = if self.is_negative {
+            if magnitude <= i128::MIN.unsigned_abs() {
+                RocDec((magnitude as i128).wrapping_neg())
+            } else {
+                return Err(self.overflow());
+            }
+        } else if magnitude <= i128::MAX as u128 {
+            RocDec(magnitude as i128)
+        } else {
+            return Err(self.overflow());
+        };
+
+        Ok((answer, is_inexact))
+    }
+
+    /// The absolute value of the number, scaled up by 10^DECIMAL_PLACES,
+    /// and whether any nonzero digits were rounded away to get it.
+    fn magnitude(&self, rounding: Option<RoundingMode>) -> Result<(u128, bool), ParseRocDecError> {
+        use ParseErrorKind::*;
+
+        let overflow = || self.overflow();
+        let digit_count = self.int_digit_count + self.frac_digits.len();
+
+        // Think of all the digits as one big integer, with the decimal point
+        // removed. Translate e.g. the 1 from 0.1 into 10000000000000000000
+        // by "restoring" the elided trailing zeroes to the number!
+        //
+        // That means scaling it by 10^shift, where shift accounts for the
+        // digits after the decimal point, the exponent, and DECIMAL_PLACES.
+        let shift = self
+            .exponent
+            .saturating_sub(self.frac_digits.len() as i64)
+            .saturating_add(RocDec::DECIMAL_PLACES as i64);
+
+        // A negative shift means the last few digits are smaller than the
+        // smallest value we can represent. That's fine if they're zeroes,
+        // or if we were asked to round them away.
+        let kept = if shift < 0 {
+            digit_count - (shift.unsigned_abs().min(digit_count as u64) as usize)
+        } else {
+            digit_count
+        };
+
+        // Accumulate the digits we're keeping. Group separators split the
+        // digits before the decimal point into runs, and the digits after it
+        // are one more run.
+        let mut answer: u128 = 0;
+        let mut remaining = kept;
+
+        if self.int_digit_count == self.int_digits.len() {
+            // There were no separators, so the digits before the decimal
+            // point are all one run. This is by far the most common case.
+            let len = self.int_digits.len().min(remaining);
+
+            answer = accumulate_digits(answer, &self.int_digits[..len]).ok_or_else(overflow)?;
+            remaining -= len;
+        } else {
+            for run in self.int_digits.split(|byte| !byte.is_ascii_digit()) {
+                let len = run.len().min(remaining);
+
+                answer = accumulate_digits(answer, &run[..len]).ok_or_else(overflow)?;
+                remaining -= len;
+            }
+        }
+
+        answer = accumulate_digits(answer, &self.frac_digits[..remaining]).ok_or_else(overflow)?;
+
+        // If the shift is so negative that even the first dropped digit is an
+        // implied leading zero, then none of the digits we have come first.
+        let first_dropped = if shift < 0 && shift.unsigned_abs() > digit_count as u64 {
+            None
+        } else {
+            Some(kept)
+        };
+
+        // How the dropped digits compare to half of the last kept digit's place.
+        let mut discarded = if first_dropped.is_none() {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        };
+        let mut is_inexact = false;
+
+        let dropped = self.digits().enumerate().skip(kept);
+
+        for (index, (offset, digit)) in dropped.take(digit_count - kept) {
+            if digit != 0 {
+                if rounding.is_none() {
+                    return Err(ParseRocDecError::new(TooManyDecimalPlaces, offset));
+                }
+
+                if Some(index) == first_dropped {
+                    discarded = digit.cmp(&5);
+                } else if discarded == Ordering::Equal {
+                    // Anything nonzero after a 5 makes it more than half.
+                    discarded = Ordering::Greater;
+                }
+
+                is_inexact = true;
+            } else if Some(index) == first_dropped {
+                discarded = Ordering::Less;
+            }
+        }
+
+        if let Some(mode) = rounding {
+            if mode.rounds_away(self.is_negative, answer % 2 == 1, discarded, is_inexact) {
+                answer = answer.checked_add(1).ok_or_else(overflow)?;
+            }
+        }
+
+        if shift > 0 && answer != 0 {
+            // 10^39 doesn't fit in a u128, so anything nonzero times it overflows.
+            if shift > 38 {
+                return Err(overflow());
+            }
+
+            answer = answer
+                .checked_mul(POWERS_OF_TEN[shift as usize])
+                .ok_or_else(overflow)?;
+        }
+
+        Ok((answer, is_inexact))
+    }
+
+    /// An Overflow error pointing at the first significant digit, since
+    /// that's the one which is too big.
+    fn overflow(&self) -> ParseRocDecError {
+        let offset = self
+            .digits()
+            .find(|(_, digit)| *digit != 0)
+            .map_or(self.int_offset, |(offset, _)| offset);
+
+        ParseRocDecError::new(ParseErrorKind::Overflow, offset)
+    }
+
+    /// Each digit's byte offset in the original string, and its value,
+    /// starting from the first digit before the decimal point.
+    fn digits(&self) -> impl Iterator<Item = (usize, u8)> + '_ {
+        let int_digits = self
+            .int_digits
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| byte.is_ascii_digit())
+            .map(move |(index, byte)| (self.int_offset + index, byte - b'0'));
+
+        let frac_digits = self
+            .frac_digits
+            .iter()
+            .enumerate()
+            .map(move |(index, byte)| (self.frac_offset + index, byte - b'0'));
+
+        int_digits.chain(frac_digits)
+    }
+}
+
+/// 10^0 through 10^38, i.e. every power of 10 which fits in a u128.
+pub(crate) const POWERS_OF_TEN: [u128; 39] = {
+    let mut powers = [1; 39];
+    let mut index = 1;
+
+    while index < powers.len() {
+        powers[index] = powers[index - 1] * 10;
+        index += 1;
+    }
+
+    powers
+};
+
+/// Append a run of ASCII digits to `answer`, returning None on overflow.
+///
+/// This handles 8 digits at a time where it can, which is considerably
+/// faster than going one digit at a time for long numbers.
+#[inline(always)]
+fn accumulate_digits(answer: u128, digits: &[u8]) -> Option<u128> {
+    let mut answer = answer;
+    let mut chunks = digits.chunks_exact(8);
+
+    for chunk in &mut chunks {
+        let mut bytes = [0; 8];
+
+        bytes.copy_from_slice(chunk);
+
+        answer = answer
+            .checked_mul(100_000_000)?
+            .checked_add(parse_8_digits(u64::from_le_bytes(bytes)) as u128)?;
+    }
+
+    for byte in chunks.remainder() {
+        answer = answer.checked_mul(10)?.checked_add((byte - b'0') as u128)?;
+    }
+
+    Some(answer)
+}
+
+/// Convert 8 ASCII digits, packed into a u64 in little-endian order (so the
+/// first digit is in the lowest byte), into the number they represent.
+///
+/// This is SWAR (SIMD within a register): each step combines adjacent pairs
+/// of numbers in parallel, so 8 digits become 4 two-digit numbers, then 2
+/// four-digit numbers, then 1 eight-digit number.
+///
+/// Based on the technique described in
+/// https://kholdstare.github.io/technical/2020/05/26/faster-integer-parsing.html
+#[inline(always)]
+fn parse_8_digits(chunk: u64) -> u64 {
+    const MASK: u64 = 0x0000_00FF_0000_00FF;
+    const MUL1: u64 = 100 + (1_000_000 << 32);
+    const MUL2: u64 = 1 + (10_000 << 32);
+
+    // Turn each ASCII byte into its digit value.
+    let chunk = chunk - 0x3030_3030_3030_3030;
+
+    // Combine each pair of adjacent digits into a 2-digit number.
+    let chunk = (chunk * 10) + (chunk >> 8);
+
+    // Combine those into two 4-digit numbers, and then those into the answer,
+    // which ends up in the upper 32 bits.
+    ((chunk & MASK).wrapping_mul(MUL1) + ((chunk >> 16) & MASK).wrapping_mul(MUL2)) >> 32
+}
+
+/// Skip digits starting at `start`, allowing a separator between any two
+/// of them. Returns the index just past the last digit, and how many digits
+/// there were.
+#[inline(always)]
+fn skip_grouped_digits(
+    bytes: &[u8],
+    start: usize,
+    separator: Option<&[u8]>,
+    loose: bool,
+) -> Result<(usize, usize), ParseRocDecError> {
+    let mut end = skip_digits(bytes, start);
+    let mut count = end - start;
+    let mut group_len = count;
+
+    if let Some(separator) = separator {
+        // Only consume a separator if there's a digit on either side of it,
+        // so that e.g. "1,,2" or "1," stop at the first ",".
+        while count > 0
+            && bytes[end..].starts_with(separator)
+            && matches!(bytes.get(end + separator.len()), Some(byte) if byte.is_ascii_digit())
+        {
+            // Later groups are checked below, so this only catches a first
+            // group that's too long, e.g. "1234,567".
+            if !loose && group_len > 3 {
+                return Err(ParseRocDecError::new(ParseErrorKind::InvalidDigit, end));
+            }
+
+            let separator_start = end;
+            let group_start = end + separator.len();
+
+            end = skip_digits(bytes, group_start);
+            group_len = end - group_start;
+            count += group_len;
+
+            if !loose && group_len != 3 {
+                return Err(ParseRocDecError::new(
+                    ParseErrorKind::InvalidDigit,
+                    separator_start,
+                ));
+            }
+        }
+    }
+
+    Ok((end, count))
+}
+
+/// Parse an optional "+" or "-" at `start`, returning whether it was a "-"
+/// and the index just after it.
+#[inline(always)]
+fn parse_sign(bytes: &[u8], start: usize) -> (bool, usize) {
+    match bytes.get(start) {
+        Some(b'-') => (true, start + 1),
+        Some(b'+') => (false, start + 1),
+        _ => (false, start),
+    }
+}
+
+/// The index of the first non-digit at or after `start`.
+#[inline(always)]
+fn skip_digits(bytes: &[u8], start: usize) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|byte| !byte.is_ascii_digit())
+        .map(|index| index + start)
+        .unwrap_or(bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParseErrorKind, ParseOptions, ParseRocDecError, RocDec, RoundingMode};
+    use std::convert::TryInto;
+
+    fn assert_parse_err(string: &str, kind: ParseErrorKind, offset: usize) {
+        let err: ParseRocDecError = string.parse::<RocDec>().unwrap_err();
+
+        assert_eq!((kind, offset), (err.kind(), err.offset()));
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(Ok(RocDec(1500000000000000000)), "1.5".parse());
+        assert_eq!(Ok(RocDec(-1500000000000000000)), "-1.5".parse());
+        assert_eq!(Ok(RocDec::MAX), super::super::MAX_STR.parse());
+        assert_eq!(Ok(RocDec::MIN), super::super::MIN_STR.parse());
+    }
+
+    #[test]
+    fn empty() {
+        assert_parse_err("", ParseErrorKind::Empty, 0);
+    }
+
+    #[test]
+    fn lenient_forms() {
+        assert_eq!(Ok(RocDec(5000000000000000000)), "5".parse());
+        assert_eq!(Ok(RocDec(5000000000000000000)), "5.".parse());
+        assert_eq!(Ok(RocDec(1500000000000000000)), "+1.5".parse());
+        assert_eq!(Ok(RocDec(500000000000000000)), ".5".parse());
+        assert_eq!(Ok(RocDec(-500000000000000000)), "-.5".parse());
+        assert_eq!(Ok(RocDec(-5000000000000000000)), "-5".parse());
+        assert_eq!(Ok(RocDec(0)), "-0".parse());
+        assert_eq!(Ok(RocDec(7000000000000000000)), "0007.000".parse());
+    }
+
+    #[test]
+    fn no_digits() {
+        assert_parse_err("-", ParseErrorKind::InvalidDigit, 1);
+        assert_parse_err("+", ParseErrorKind::InvalidDigit, 1);
+        assert_parse_err(".", ParseErrorKind::InvalidDigit, 1);
+        assert_parse_err("-.", ParseErrorKind::InvalidDigit, 2);
+    }
+
+    #[test]
+    fn invalid_digit() {
+        assert_parse_err("1x.5", ParseErrorKind::InvalidDigit, 1);
+        assert_parse_err("12.3y", ParseErrorKind::InvalidDigit, 4);
+        assert_parse_err("1.2.3", ParseErrorKind::InvalidDigit, 3);
+        assert_parse_err("1. 5", ParseErrorKind::InvalidDigit, 2);
+    }
+
+    #[test]
+    fn embedded_signs() {
+        assert_parse_err("1.+5", ParseErrorKind::InvalidDigit, 2);
+        assert_parse_err("1.-5", ParseErrorKind::InvalidDigit, 2);
+        assert_parse_err("--1.5", ParseErrorKind::InvalidDigit, 1);
+        assert_parse_err("+-1.5", ParseErrorKind::InvalidDigit, 1);
+        assert_parse_err("1-5", ParseErrorKind::InvalidDigit, 1);
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_parse_err(" 1.5", ParseErrorKind::InvalidDigit, 0);
+        assert_parse_err("1.5 ", ParseErrorKind::InvalidDigit, 3);
+        assert_parse_err("- 1.5", ParseErrorKind::InvalidDigit, 1);
+        assert_parse_err("1 000.5", ParseErrorKind::InvalidDigit, 1);
+    }
+
+    #[test]
+    fn too_many_decimal_places() {
+        assert_parse_err(
+            "0.0000000000000000001",
+            ParseErrorKind::TooManyDecimalPlaces,
+            20,
+        );
+    }
+
+    #[test]
+    fn trailing_zeroes_beyond_decimal_places() {
+        assert_eq!(
+            Ok(RocDec(1500000000000000000)),
+            "1.500000000000000000000000000000000000000000".parse()
+        );
+    }
+
+    #[test]
+    fn exponent() {
+        assert_eq!(Ok(RocDec(1500000000000000)), "1.5e-3".parse());
+        assert_eq!(Ok(RocDec(20000000000000000000000000000)), "2E10".parse());
+        assert_eq!(Ok(RocDec(-725000000000000000000)), "-7.25e+2".parse());
+        assert_eq!(Ok(RocDec(1)), "1e-18".parse());
+        assert_eq!(Ok(RocDec(1)), "100e-20".parse());
+        assert_eq!(Ok(RocDec(1500000000000000000)), ".15e1".parse());
+        assert_eq!(Ok(RocDec(1500000000000000000)), "15.e-1".parse());
+        assert_eq!(Ok(RocDec(0)), "0e999999999999999999999999".parse());
+        assert_eq!(Ok(RocDec(0)), "0.0e-999999999999999999999999".parse());
+        assert_eq!(
+            Ok(RocDec(123456789012345678901)),
+            "1.23456789012345678901e2".parse()
+        );
+        assert_eq!(
+            Ok(RocDec::MAX),
+            "1.70141183460469231731687303715884105727E20".parse()
+        );
+        assert_eq!(
+            Ok(RocDec::MIN),
+            "-170141183460469231731687303715884105728e-18".parse()
+        );
+    }
+
+    #[test]
+    fn exponent_invalid() {
+        assert_parse_err("1e", ParseErrorKind::InvalidDigit, 2);
+        assert_parse_err("1e+", ParseErrorKind::InvalidDigit, 3);
+        assert_parse_err("1e1.5", ParseErrorKind::InvalidDigit, 3);
+        assert_parse_err("1e5e5", ParseErrorKind::InvalidDigit, 3);
+        assert_parse_err("e5", ParseErrorKind::InvalidDigit, 0);
+        assert_parse_err(".e5", ParseErrorKind::InvalidDigit, 1);
+        assert_parse_err("1 e5", ParseErrorKind::InvalidDigit, 1);
+    }
+
+    #[test]
+    fn exponent_too_many_decimal_places() {
+        assert_parse_err("1e-19", ParseErrorKind::TooManyDecimalPlaces, 0);
+        assert_parse_err("1.25e-17", ParseErrorKind::TooManyDecimalPlaces, 3);
+        assert_parse_err(
+            "1e-999999999999999999999999",
+            ParseErrorKind::TooManyDecimalPlaces,
+            0,
+        );
+    }
+
+    #[test]
+    fn exponent_overflow() {
+        assert_parse_err("1e21", ParseErrorKind::Overflow, 0);
+        assert_parse_err("-1.8e20", ParseErrorKind::Overflow, 1);
+        assert_parse_err("0.002e25", ParseErrorKind::Overflow, 4);
+        assert_parse_err("1e999999999999999999999999", ParseErrorKind::Overflow, 0);
+    }
+
+    #[test]
+    fn overflow() {
+        assert_parse_err(
+            "170141183460469231731.687303715884105728",
+            ParseErrorKind::Overflow,
+            0,
+        );
+        assert_parse_err(
+            "1000000000000000000000000000000000000000.0",
+            ParseErrorKind::Overflow,
+            0,
+        );
+        assert_parse_err("-00170141183460469231732.0", ParseErrorKind::Overflow, 3);
+    }
+
+    fn assert_parse_with(string: &str, options: &ParseOptions, expected: &str) {
+        let expected: RocDec = expected.try_into().unwrap();
+
+        assert_eq!(Ok(expected), RocDec::parse_with(string, options));
+    }
+
+    fn assert_parse_with_err(
+        string: &str,
+        options: &ParseOptions,
+        kind: ParseErrorKind,
+        offset: usize,
+    ) {
+        let err = RocDec::parse_with(string, options).unwrap_err();
+
+        assert_eq!((kind, offset), (err.kind(), err.offset()));
+    }
+
+    #[test]
+    fn parse_with_defaults() {
+        let options = ParseOptions::new();
+
+        assert_parse_with("-1.5e2", &options, "-150.0");
+        assert_parse_with_err("1,5", &options, ParseErrorKind::InvalidDigit, 1);
+        assert_parse_with_err(" 1.5", &options, ParseErrorKind::InvalidDigit, 0);
+    }
+
+    #[test]
+    fn group_separators() {
+        let underscore = ParseOptions::new().group_separator('_');
+        let comma = ParseOptions::new().group_separator(',');
+        let apostrophe = ParseOptions::new().group_separator('\'');
+        let space = ParseOptions::new().group_separator(' ');
+
+        assert_parse_with("1_000_000.5", &underscore, "1000000.5");
+        assert_parse_with("-10_000.5", &underscore, "-10000.5");
+        assert_parse_with("1,234.56", &comma, "1234.56");
+        assert_parse_with("1'234.50", &apostrophe, "1234.5");
+        assert_parse_with("12 345 678", &space, "12345678.0");
+        assert_parse_with("1234567.5", &comma, "1234567.5");
+    }
+
+    #[test]
+    fn group_sizes() {
+        let comma = ParseOptions::new().group_separator(',');
+
+        assert_parse_with_err("1,23", &comma, ParseErrorKind::InvalidDigit, 1);
+        assert_parse_with_err("1,2345", &comma, ParseErrorKind::InvalidDigit, 1);
+        assert_parse_with_err("1,234,56.5", &comma, ParseErrorKind::InvalidDigit, 5);
+        assert_parse_with_err("12,34,567.0", &comma, ParseErrorKind::InvalidDigit, 2);
+        assert_parse_with_err("1234,567", &comma, ParseErrorKind::InvalidDigit, 4);
+        assert_parse_with_err("-1234,567.5", &comma, ParseErrorKind::InvalidDigit, 5);
+
+        let loose = comma.loose_grouping(true);
+
+        assert_parse_with("12,34,567.0", &loose, "1234567.0");
+        assert_parse_with("1234,567", &loose, "1234567.0");
+        assert_parse_with("-1,0.5", &loose, "-10.5");
+        assert_parse_with_err("1,,0", &loose, ParseErrorKind::InvalidDigit, 1);
+    }
+
+    #[test]
+    fn same_separators() {
+        let options = ParseOptions::new()
+            .group_separator(',')
+            .decimal_separator(',');
+
+        assert_parse_with_err("1,5", &options, ParseErrorKind::SameSeparators, 0);
+        assert_parse_with_err(
+            "1.5",
+            &ParseOptions::new().group_separator('.'),
+            ParseErrorKind::SameSeparators,
+            0,
+        );
+    }
+
+    #[test]
+    fn group_separators_need_digits_on_both_sides() {
+        let underscore = ParseOptions::new().group_separator('_');
+
+        assert_parse_with_err("_1", &underscore, ParseErrorKind::InvalidDigit, 0);
+        assert_parse_with_err("1_", &underscore, ParseErrorKind::InvalidDigit, 1);
+        assert_parse_with_err("1__000", &underscore, ParseErrorKind::InvalidDigit, 1);
+        assert_parse_with_err("1_.5", &underscore, ParseErrorKind::InvalidDigit, 1);
+        assert_parse_with_err("1._5", &underscore, ParseErrorKind::InvalidDigit, 2);
+        assert_parse_with_err("1.5_000", &underscore, ParseErrorKind::InvalidDigit, 3);
+    }
+
+    #[test]
+    fn decimal_comma() {
+        let european = ParseOptions::new()
+            .group_separator('.')
+            .decimal_separator(',');
+
+        assert_parse_with("1.234.567,89", &european, "1234567.89");
+        assert_parse_with("-0,5", &european, "-0.5");
+        assert_parse_with("12", &european, "12.0");
+        assert_parse_with("1,5e3", &european, "1500.0");
+
+        // With "." as the group separator, "1.5" and "1.23" aren't numbers,
+        // rather than being read as fifteen and one hundred twenty-three.
+        assert_parse_with_err("1.5", &european, ParseErrorKind::InvalidDigit, 1);
+        assert_parse_with_err("1.23", &european, ParseErrorKind::InvalidDigit, 1);
+    }
+
+    #[test]
+    fn multibyte_separators() {
+        let swiss = ParseOptions::new().group_separator('’');
+        let french = ParseOptions::new()
+            .group_separator('\u{a0}')
+            .decimal_separator(',');
+
+        assert_parse_with("1’234’567.5", &swiss, "1234567.5");
+        assert_parse_with("1\u{a0}234,5", &french, "1234.5");
+        assert_parse_with_err(
+            "1’234.00000000000000000001",
+            &swiss,
+            ParseErrorKind::TooManyDecimalPlaces,
+            27,
+        );
+    }
+
+    #[test]
+    fn trim_whitespace() {
+        let options = ParseOptions::new().trim_whitespace(true);
+
+        assert_parse_with("  1.5\t\n", &options, "1.5");
+        assert_parse_with_err("   ", &options, ParseErrorKind::Empty, 3);
+        assert_parse_with_err(" 1 .5 ", &options, ParseErrorKind::InvalidDigit, 2);
+    }
+
+    #[test]
+    fn parentheses() {
+        let options = ParseOptions::new()
+            .allow_parentheses(true)
+            .group_separator(',')
+            .trim_whitespace(true);
+
+        assert_parse_with("(45.00)", &options, "-45.0");
+        assert_parse_with(" (1,234.5) ", &options, "-1234.5");
+        assert_parse_with("45.00", &options, "45.0");
+        assert_parse_with_err("(45.00", &options, ParseErrorKind::InvalidDigit, 6);
+        assert_parse_with_err("()", &options, ParseErrorKind::InvalidDigit, 1);
+        assert_parse_with_err("(-45.00)", &options, ParseErrorKind::InvalidDigit, 1);
+        assert_parse_with_err(
+            "(45.00)",
+            &ParseOptions::new(),
+            ParseErrorKind::InvalidDigit,
+            0,
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn digit_separator() {
+        ParseOptions::new().group_separator('0');
+    }
+
+    fn assert_parse_rounded(string: &str, mode: RoundingMode, expected: &str, is_inexact: bool) {
+        let expected: RocDec = expected.try_into().unwrap();
+
+        assert_eq!(
+            Ok((expected, is_inexact)),
+            RocDec::parse_rounded(string, mode)
+        );
+    }
+
+    #[test]
+    fn parse_rounded_exact() {
+        assert_parse_rounded("1.5", RoundingMode::HalfEven, "1.5", false);
+        assert_parse_rounded(
+            "1.0000000000000000010000",
+            RoundingMode::Up,
+            "1.000000000000000001",
+            false,
+        );
+    }
+
+    #[test]
+    fn parse_rounded_modes() {
+        // 20 decimal places: ...001 followed by "49", "50" or "51".
+        let below_half = "0.00000000000000000149";
+        let half = "0.00000000000000000150";
+        let above_half = "0.00000000000000000151";
+        let one = "0.000000000000000001";
+        let two = "0.000000000000000002";
+
+        assert_parse_rounded(below_half, RoundingMode::HalfUp, one, true);
+        assert_parse_rounded(half, RoundingMode::HalfUp, two, true);
+        assert_parse_rounded(half, RoundingMode::HalfDown, one, true);
+        assert_parse_rounded(above_half, RoundingMode::HalfDown, two, true);
+        assert_parse_rounded(half, RoundingMode::HalfEven, two, true);
+        assert_parse_rounded("0.00000000000000000250", RoundingMode::HalfEven, two, true);
+        assert_parse_rounded(
+            "0.000000000000000002500000000000001",
+            RoundingMode::HalfEven,
+            "0.000000000000000003",
+            true,
+        );
+        assert_parse_rounded(above_half, RoundingMode::Down, one, true);
+        assert_parse_rounded(below_half, RoundingMode::Up, two, true);
+        assert_parse_rounded(below_half, RoundingMode::Floor, one, true);
+        assert_parse_rounded(below_half, RoundingMode::Ceiling, two, true);
+    }
+
+    #[test]
+    fn parse_rounded_negative() {
+        let half = "-0.00000000000000000150";
+
+        assert_parse_rounded(half, RoundingMode::HalfUp, "-0.000000000000000002", true);
+        assert_parse_rounded(half, RoundingMode::Floor, "-0.000000000000000002", true);
+        assert_parse_rounded(half, RoundingMode::Ceiling, "-0.000000000000000001", true);
+    }
+
+    #[test]
+    fn parse_rounded_carries() {
+        assert_parse_rounded("9.9999999999999999999", RoundingMode::HalfUp, "10.0", true);
+        assert_parse_rounded(
+            "0.0000000000000000005",
+            RoundingMode::HalfUp,
+            "0.000000000000000001",
+            true,
+        );
+        assert_parse_rounded("0.0000000000000000005", RoundingMode::HalfEven, "0.0", true);
+    }
+
+    #[test]
+    fn parse_rounded_tiny() {
+        // The dropped digits start well past the first one we'd keep.
+        assert_parse_rounded("5e-25", RoundingMode::HalfUp, "0.0", true);
+        assert_parse_rounded("5e-25", RoundingMode::Up, "0.000000000000000001", true);
+        assert_parse_rounded("0e-25", RoundingMode::Up, "0.0", false);
+    }
+
+    #[test]
+    fn parse_rounded_overflow() {
+        assert_eq!(
+            ParseErrorKind::Overflow,
+            RocDec::parse_rounded(
+                "170141183460469231731.6873037158841057275",
+                RoundingMode::HalfUp
+            )
+            .unwrap_err()
+            .kind()
+        );
+        assert_parse_rounded(
+            "170141183460469231731.6873037158841057275",
+            RoundingMode::Down,
+            super::super::MAX_STR,
+            true,
+        );
+    }
+
+    #[test]
+    fn parse_bytes() {
+        assert_eq!(
+            Ok(RocDec(-1500000000000000000)),
+            RocDec::parse_bytes(b"-1.5")
+        );
+        assert_eq!(
+            Ok(RocDec::MIN),
+            RocDec::parse_bytes(super::super::MIN_STR.as_bytes())
+        );
+        assert_eq!(
+            ParseErrorKind::InvalidDigit,
+            RocDec::parse_bytes(b"1.\xff").unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn parse_8_digits() {
+        let parse = |digits: &[u8; 8]| super::parse_8_digits(u64::from_le_bytes(*digits));
+
+        assert_eq!(12345678, parse(b"12345678"));
+        assert_eq!(0, parse(b"00000000"));
+        assert_eq!(99999999, parse(b"99999999"));
+        assert_eq!(1, parse(b"00000001"));
+        assert_eq!(10000000, parse(b"10000000"));
+        assert_eq!(90807060, parse(b"90807060"));
+    }
+
+    #[test]
+    fn long_digit_runs() {
+        // Lengths around multiples of 8, to exercise the chunked and
+        // leftover digit paths.
+        for len in 1..=20 {
+            let digits: String = "987654321098765432109".chars().take(len).collect();
+            let expected = digits.parse::<u128>().unwrap() * 10u128.pow(18);
+
+            assert_eq!(Ok(RocDec(expected as i128)), digits.parse());
+        }
+
+        assert_eq!(
+            Ok(RocDec(123456789012345678)),
+            "0.123456789012345678".parse()
+        );
+        assert_eq!(
+            Ok(RocDec(1)),
+            "0000000000000000000000000000000000000000.000000000000000001".parse()
+        );
+    }
+
+    #[test]
+    fn dec_macro() {
+        const FEE_RATE: RocDec = crate::dec!("0.0025");
+        static LIMIT: RocDec = crate::dec!("-1000.5");
+
+        assert_eq!(Ok(FEE_RATE), "0.0025".parse());
+        assert_eq!(Ok(LIMIT), "-1000.5".parse());
+    }
+
+    #[test]
+    fn from_str_const() {
+        for string in &[
+            "0",
+            "-0",
+            "+7",
+            "5.",
+            ".5",
+            "-.5",
+            "123.456",
+            "1.000000000000000000000",
+            "0000000000000000000000000000000000000000.000000000000000001",
+            super::super::MAX_STR,
+            super::super::MIN_STR,
+        ] {
+            assert_eq!(
+                string.parse::<RocDec>(),
+                Ok(RocDec::from_str_const(string)),
+                "{:?}",
+                string
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid digit")]
+    fn from_str_const_invalid_digit() {
+        RocDec::from_str_const("1.2.3");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid digit")]
+    fn from_str_const_no_digits() {
+        RocDec::from_str_const("-.");
+    }
+
+    #[test]
+    #[should_panic(expected = "too many decimal places")]
+    fn from_str_const_too_many_decimal_places() {
+        RocDec::from_str_const("0.0000000000000000001");
+    }
+
+    #[test]
+    #[should_panic(expected = "too large or too small")]
+    fn from_str_const_overflow() {
+        RocDec::from_str_const("170141183460469231731.687303715884105728");
+    }
+
+    #[test]
+    #[should_panic(expected = "empty string")]
+    fn from_str_const_empty() {
+        RocDec::from_str_const("");
+    }
+
+    #[test]
+    fn error_display() {
+        assert_eq!(
+            "invalid digit found in decimal at byte 1",
+            "1x.5".parse::<RocDec>().unwrap_err().to_string()
+        );
+    }
+}
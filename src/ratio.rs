@@ -0,0 +1,298 @@
+use crate::{div_u256_by_u128, mul_u128, RocDec, RoundingMode, U256};
+
+impl RocDec {
+    /// The exact value as a fraction in lowest terms, e.g. (-3, 4) for
+    /// -0.75. The denominator is always a divisor of 10^18, and zero is
+    /// (0, 1).
+    pub fn to_ratio(&self) -> (i128, u128) {
+        let denominator = Self::ONE_POINT_ZERO as u128;
+        let divisor = gcd(self.0.unsigned_abs(), denominator);
+
+        // The divisor is positive and divides both exactly, so neither of
+        // these can overflow, even for RocDec::MIN.
+        (self.0 / divisor as i128, denominator / divisor)
+    }
+
+    /// The fraction `numerator / denominator`, rounded to 18 decimal places
+    /// with the given mode. Returns None if the denominator is zero or the
+    /// answer doesn't fit in a RocDec.
+    ///
+    /// This is exact whenever the denominator divides 10^18, so it undoes
+    /// `to_ratio`.
+    pub fn from_ratio(numerator: i128, denominator: u128, mode: RoundingMode) -> Option<RocDec> {
+        if denominator == 0 {
+            return None;
+        }
+
+        let is_negative = numerator.is_negative();
+
+        // This is at most 2^127 * 10^18, which always fits in a U256.
+        let scaled = mul_u128(numerator.unsigned_abs(), Self::ONE_POINT_ZERO as u128);
+        let quotient = div_u256_by_u128(scaled, denominator);
+
+        if quotient.hi != 0 {
+            return None;
+        }
+
+        // The remainder is less than the denominator, so it fits in the low half.
+        let rem = scaled.wrapping_sub(mul_u128(quotient.lo, denominator)).lo;
+
+        // Comparing against denominator - rem rather than denominator / 2
+        // gets ties right for odd denominators too.
+        let magnitude = if mode.rounds_away(
+            is_negative,
+            quotient.lo % 2 == 1,
+            rem.cmp(&(denominator - rem)),
+            rem != 0,
+        ) {
+            quotient.lo.checked_add(1)?
+        } else {
+            quotient.lo
+        };
+
+        let magnitude = U256 {
+            hi: 0,
+            lo: magnitude,
+        };
+
+        if is_negative {
+            magnitude.wrapping_neg().checked_to_i128().map(RocDec)
+        } else {
+            magnitude.checked_to_i128().map(RocDec)
+        }
+    }
+
+    /// The fraction closest to this value whose denominator is at most
+    /// `max_denominator`, in lowest terms, e.g. (1, 3) for 0.333333333333333333
+    /// with a `max_denominator` of 100.
+    ///
+    /// This walks the continued fraction expansion of the exact ratio, and
+    /// then checks the best semiconvergent at the point where the next
+    /// convergent's denominator would be too big.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_denominator` is zero.
+    pub fn approximate_ratio(&self, max_denominator: u128) -> (i128, u128) {
+        assert!(
+            max_denominator > 0,
+            "cannot approximate a RocDec with a denominator of zero"
+        );
+
+        let (numerator, denominator) = self.to_ratio();
+
+        if denominator <= max_denominator {
+            return (numerator, denominator);
+        }
+
+        let is_negative = numerator.is_negative();
+        let (n, d) = (numerator.unsigned_abs(), denominator);
+
+        // The last two convergents, h1 / k1 and the one before it, h0 / k0.
+        // These start out as the conventional 1 / 0 and 0 / 1.
+        let (mut h0, mut k0) = (0u128, 1u128);
+        let (mut h1, mut k1) = (1u128, 0u128);
+
+        // The remainders from running Euclid's algorithm on n and d. These
+        // are also how far off each convergent is: |n * k0 - d * h0| is
+        // always `num`, and |n * k1 - d * h1| is always `den`.
+        let (mut num, mut den) = (n, d);
+
+        let (h, k) = loop {
+            let a = num / den;
+            let k2 = a * k1 + k0;
+
+            if k2 > max_denominator {
+                // The next convergent's denominator is too big, so the best
+                // answer is either h1 / k1, or the largest semiconvergent
+                // (h0 + t * h1) / (k0 + t * k1) whose denominator fits.
+                // k1 can't be 0 here, because the first convergent is
+                // always a whole number.
+                let t = (max_denominator - k0) / k1;
+                let (hs, ks) = (h0 + t * h1, k0 + t * k1);
+
+                // The errors are den / (d * k1) and (num - t * den) / (d * ks),
+                // so cross-multiplying compares them exactly.
+                break if mul_u128(den, ks) <= mul_u128(num - t * den, k1) {
+                    (h1, k1)
+                } else {
+                    (hs, ks)
+                };
+            }
+
+            let h2 = a * h1 + h0;
+            let rem = num - a * den;
+
+            h0 = h1;
+            k0 = k1;
+            h1 = h2;
+            k1 = k2;
+
+            // The ratio is in lowest terms and its denominator is bigger
+            // than max_denominator, so we always stop above before this
+            // runs out of remainders.
+            debug_assert!(rem != 0);
+
+            num = den;
+            den = rem;
+        };
+
+        // h is at most n, which came from an i128, so this can't overflow.
+        let h = h as i128;
+
+        (if is_negative { -h } else { h }, k)
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let rem = a % b;
+
+        a = b;
+        b = rem;
+    }
+
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RocDec, RoundingMode};
+    use std::convert::TryInto;
+
+    fn dec(string: &str) -> RocDec {
+        string.try_into().unwrap()
+    }
+
+    #[test]
+    fn to_ratio() {
+        assert_eq!((0, 1), dec("0.0").to_ratio());
+        assert_eq!((5, 1), dec("5.0").to_ratio());
+        assert_eq!((-3, 4), dec("-0.75").to_ratio());
+        assert_eq!((1, 8), dec("0.125").to_ratio());
+        assert_eq!((1, 1_000_000_000_000_000_000), RocDec(1).to_ratio());
+        assert_eq!(
+            (333_333_333_333_333_333, 1_000_000_000_000_000_000),
+            dec("0.333333333333333333").to_ratio()
+        );
+        assert_eq!(
+            (i128::MIN / (1 << 18), 3_814_697_265_625),
+            RocDec::MIN.to_ratio()
+        );
+        assert_eq!(
+            (i128::MAX, 1_000_000_000_000_000_000),
+            RocDec::MAX.to_ratio()
+        );
+    }
+
+    #[test]
+    fn from_ratio() {
+        assert_eq!(
+            Some(dec("0.333333333333333333")),
+            RocDec::from_ratio(1, 3, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Some(dec("0.666666666666666667")),
+            RocDec::from_ratio(2, 3, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Some(dec("-0.666666666666666666")),
+            RocDec::from_ratio(-2, 3, RoundingMode::Down)
+        );
+        assert_eq!(
+            Some(dec("-0.666666666666666667")),
+            RocDec::from_ratio(-2, 3, RoundingMode::Floor)
+        );
+        assert_eq!(
+            Some(dec("0.333333333333333334")),
+            RocDec::from_ratio(1, 3, RoundingMode::Ceiling)
+        );
+        assert_eq!(
+            Some(dec("-2.5")),
+            RocDec::from_ratio(-5, 2, RoundingMode::Up)
+        );
+        assert_eq!(None, RocDec::from_ratio(1, 0, RoundingMode::HalfEven));
+        assert_eq!(
+            None,
+            RocDec::from_ratio(i128::MAX, 1, RoundingMode::HalfEven)
+        );
+    }
+
+    #[test]
+    fn from_ratio_ties() {
+        // 1 / (2 * 10^18) is exactly half of the smallest RocDec.
+        let half_ulp = 2_000_000_000_000_000_000;
+
+        assert_eq!(
+            Some(RocDec(0)),
+            RocDec::from_ratio(1, half_ulp, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Some(RocDec(1)),
+            RocDec::from_ratio(1, half_ulp, RoundingMode::HalfUp)
+        );
+        assert_eq!(
+            Some(RocDec(2)),
+            RocDec::from_ratio(3, half_ulp, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            Some(RocDec(-1)),
+            RocDec::from_ratio(-3, half_ulp, RoundingMode::HalfDown)
+        );
+    }
+
+    #[test]
+    fn from_ratio_undoes_to_ratio() {
+        for dec in &[
+            RocDec::MIN,
+            RocDec::MAX,
+            RocDec(1),
+            RocDec(-1),
+            dec("-1234.5678"),
+            dec("0.333333333333333333"),
+        ] {
+            let (numerator, denominator) = dec.to_ratio();
+
+            assert_eq!(
+                Some(*dec),
+                RocDec::from_ratio(numerator, denominator, RoundingMode::Down)
+            );
+        }
+    }
+
+    #[test]
+    fn approximate_ratio() {
+        assert_eq!((1, 3), dec("0.333333333333333333").approximate_ratio(100));
+        assert_eq!(
+            (-2, 3),
+            dec("-0.666666666666666667").approximate_ratio(1000)
+        );
+        assert_eq!((22, 7), dec("3.141592653589793238").approximate_ratio(10));
+        assert_eq!(
+            (311, 99),
+            dec("3.141592653589793238").approximate_ratio(100)
+        );
+        assert_eq!(
+            (355, 113),
+            dec("3.141592653589793238").approximate_ratio(1000)
+        );
+        assert_eq!((3, 1), dec("3.141592653589793238").approximate_ratio(1));
+        assert_eq!((3, 4), dec("0.75").approximate_ratio(4));
+        assert_eq!((0, 1), dec("0.0").approximate_ratio(1));
+        assert_eq!((0, 1), RocDec(1).approximate_ratio(1000));
+        assert_eq!((1, 1), dec("0.999999").approximate_ratio(1000));
+    }
+
+    #[test]
+    fn approximate_ratio_ties() {
+        // 0.5 is equally close to 0 / 1 and 1 / 1.
+        assert_eq!((0, 1), dec("0.5").approximate_ratio(1));
+        assert_eq!((0, 1), dec("-0.5").approximate_ratio(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "denominator of zero")]
+    fn approximate_ratio_zero() {
+        dec("1.5").approximate_ratio(0);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::{RocDec, RocDecAccumulator};
+use std::collections::VecDeque;
+
+/// Aggregations over the most recent `capacity` values pushed into it:
+/// a simple moving average, and a rolling sum, min, and max.
+///
+/// Each push takes amortized constant time, no matter how large the window.
+/// The sum is updated by adding the new value and subtracting the one that
+/// fell out of the window, rather than by re-summing the whole window, and
+/// since it's kept in a RocDecAccumulator, it can't drift or overflow.
+#[derive(Clone, Debug)]
+pub struct RollingWindow {
+    capacity: usize,
+    values: VecDeque<RocDec>,
+    sum: RocDecAccumulator,
+
+    // How many values have ever been pushed. Used to identify which entries
+    // in the min and max queues have fallen out of the window.
+    pushed: u64,
+
+    // Monotonic queues of (push number, value). The front of `mins` is always
+    // the window's minimum, and each entry after it is greater than the one
+    // before. Any value with a smaller value pushed after it can never be the
+    // minimum again, so it gets dropped. (Likewise for `maxes`.)
+    mins: VecDeque<(u64, RocDec)>,
+    maxes: VecDeque<(u64, RocDec)>,
+}
+
+impl RollingWindow {
+    /// Panics if capacity is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "a RollingWindow needs a capacity of at least 1"
+        );
+
+        RollingWindow {
+            capacity,
+            values: VecDeque::with_capacity(capacity),
+            sum: RocDecAccumulator::new(),
+            pushed: 0,
+            mins: VecDeque::new(),
+            maxes: VecDeque::new(),
+        }
+    }
+
+    /// Add a value to the window, evicting the oldest one if the window is full.
+    pub fn push(&mut self, dec: RocDec) {
+        if self.values.len() == self.capacity {
+            if let Some(evicted) = self.values.pop_front() {
+                self.sum.sub(evicted);
+            }
+
+            // The push number of the value we just evicted.
+            let evicted = self.pushed - self.capacity as u64;
+
+            if matches!(self.mins.front(), Some((pushed, _)) if *pushed == evicted) {
+                self.mins.pop_front();
+            }
+
+            if matches!(self.maxes.front(), Some((pushed, _)) if *pushed == evicted) {
+                self.maxes.pop_front();
+            }
+        }
+
+        self.values.push_back(dec);
+        self.sum.add(dec);
+
+        while matches!(self.mins.back(), Some((_, min)) if *min >= dec) {
+            self.mins.pop_back();
+        }
+
+        while matches!(self.maxes.back(), Some((_, max)) if *max <= dec) {
+            self.maxes.pop_back();
+        }
+
+        self.mins.push_back((self.pushed, dec));
+        self.maxes.push_back((self.pushed, dec));
+        self.pushed += 1;
+    }
+
+    /// How many values are currently in the window.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the window has no values in it yet.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Whether the window has seen at least `capacity` values.
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.capacity
+    }
+
+    /// The most values the window holds at once.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The sum of the values in the window, or None if it doesn't fit in a RocDec.
+    pub fn sum(&self) -> Option<RocDec> {
+        self.sum.finish()
+    }
+
+    /// The simple moving average of the values in the window, truncated
+    /// toward zero. Returns None if the window is empty.
+    pub fn mean(&self) -> Option<RocDec> {
+        if self.values.is_empty() {
+            return None;
+        }
+
+        // usize is at most 64 bits, so this can't truncate.
+        let len = RocDec::from(self.values.len() as u64);
+
+        self.sum.checked_div(len)
+    }
+
+    /// The smallest value in the window, or None if it's empty.
+    pub fn min(&self) -> Option<RocDec> {
+        self.mins.front().map(|(_, min)| *min)
+    }
+
+    /// The largest value in the window, or None if it's empty.
+    pub fn max(&self) -> Option<RocDec> {
+        self.maxes.front().map(|(_, max)| *max)
+    }
+}
+
+/// An exponential moving average: each new value moves the average
+/// `alpha` of the way toward it.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ExponentialMovingAverage {
+    alpha: RocDec,
+    average: Option<RocDec>,
+}
+
+impl ExponentialMovingAverage {
+    /// Panics unless 0 < alpha <= 1.
+    pub fn new(alpha: RocDec) -> Self {
+        assert!(
+            alpha.0 > 0 && alpha.0 <= RocDec::ONE_POINT_ZERO,
+            "an ExponentialMovingAverage needs an alpha between 0 (exclusive) and 1 (inclusive)"
+        );
+
+        ExponentialMovingAverage {
+            alpha,
+            average: None,
+        }
+    }
+
+    /// Update the average with a new value. The first value pushed becomes
+    /// the initial average.
+    pub fn push(&mut self, dec: RocDec) {
+        let average = match self.average {
+            None => dec,
+            Some(prev) => {
+                // prev + alpha * (dec - prev), computed as
+                // prev + alpha * dec - alpha * prev so that neither the
+                // subtraction nor the products can overflow or lose digits
+                // before the final truncation.
+                let mut acc = RocDecAccumulator::new();
+
+                acc.add(prev);
+                acc.add_product(self.alpha, dec);
+                acc.add_product(-self.alpha, prev);
+
+                // The answer is always between prev and dec, so it fits.
+                acc.finish_saturating()
+            }
+        };
+
+        self.average = Some(average);
+    }
+
+    /// The current average, or None if nothing has been pushed yet.
+    pub fn average(&self) -> Option<RocDec> {
+        self.average
+    }
+
+    /// How far each push moves the average toward the new value.
+    pub fn alpha(&self) -> RocDec {
+        self.alpha
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExponentialMovingAverage, RollingWindow};
+    use crate::RocDec;
+    use std::convert::TryInto;
+
+    fn dec(string: &str) -> RocDec {
+        string.try_into().unwrap()
+    }
+
+    fn assert_window(window: &RollingWindow, sum: &str, mean: &str, min: &str, max: &str) {
+        assert_eq!(Some(dec(sum)), window.sum());
+        assert_eq!(Some(dec(mean)), window.mean());
+        assert_eq!(Some(dec(min)), window.min());
+        assert_eq!(Some(dec(max)), window.max());
+    }
+
+    #[test]
+    fn empty_window() {
+        let window = RollingWindow::new(3);
+
+        assert!(window.is_empty());
+        assert_eq!(Some(RocDec(0)), window.sum());
+        assert_eq!(None, window.mean());
+        assert_eq!(None, window.min());
+        assert_eq!(None, window.max());
+    }
+
+    #[test]
+    fn window_slides() {
+        let mut window = RollingWindow::new(3);
+
+        window.push(dec("1.0"));
+        assert_window(&window, "1.0", "1.0", "1.0", "1.0");
+
+        window.push(dec("5.0"));
+        window.push(dec("3.0"));
+        assert!(window.is_full());
+        assert_window(&window, "9.0", "3.0", "1.0", "5.0");
+
+        // 1.0 falls out of the window.
+        window.push(dec("2.0"));
+        assert_eq!(3, window.len());
+        assert_window(&window, "10.0", "3.333333333333333333", "2.0", "5.0");
+
+        // Then 5.0 does.
+        window.push(dec("4.0"));
+        assert_window(&window, "9.0", "3.0", "2.0", "4.0");
+
+        window.push(dec("-1.0"));
+        window.push(dec("-1.0"));
+        assert_window(&window, "2.0", "0.666666666666666666", "-1.0", "4.0");
+
+        window.push(dec("-1.0"));
+        assert_window(&window, "-3.0", "-1.0", "-1.0", "-1.0");
+    }
+
+    #[test]
+    fn window_of_one() {
+        let mut window = RollingWindow::new(1);
+
+        for string in &["3.0", "-2.5", "7.25"] {
+            window.push(dec(string));
+            assert_window(&window, string, string, string, string);
+        }
+    }
+
+    #[test]
+    fn window_sum_exceeds_max_temporarily() {
+        let mut window = RollingWindow::new(2);
+
+        window.push(RocDec::MAX);
+        window.push(RocDec::MAX);
+
+        assert_eq!(None, window.sum());
+        assert_eq!(Some(RocDec::MAX), window.mean());
+
+        window.push(-RocDec::MAX);
+
+        assert_eq!(Some(RocDec(0)), window.sum());
+    }
+
+    #[test]
+    fn ema() {
+        let mut ema = ExponentialMovingAverage::new(dec("0.5"));
+
+        assert_eq!(None, ema.average());
+
+        ema.push(dec("10.0"));
+        assert_eq!(Some(dec("10.0")), ema.average());
+
+        ema.push(dec("20.0"));
+        assert_eq!(Some(dec("15.0")), ema.average());
+
+        ema.push(dec("0.0"));
+        assert_eq!(Some(dec("7.5")), ema.average());
+    }
+
+    #[test]
+    fn ema_extremes() {
+        let mut ema = ExponentialMovingAverage::new(dec("0.5"));
+
+        // The difference between these doesn't fit in a RocDec.
+        ema.push(RocDec::MAX);
+        ema.push(RocDec::MIN);
+
+        assert_eq!(Some(RocDec(0)), ema.average());
+    }
+
+    #[test]
+    #[should_panic]
+    fn ema_zero_alpha() {
+        ExponentialMovingAverage::new(RocDec(0));
+    }
+}
@@ -0,0 +1,463 @@
+use crate::{div_u256_by_u128, mul_i128, mul_u128, RocDec, RocDecAccumulator, U256};
+
+/// How `percentile` picks an answer when the requested rank falls between
+/// two elements. These match the methods of the same names in NumPy.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Interpolation {
+    /// Interpolate linearly between the two neighboring elements.
+    Linear,
+    /// Use the lower of the two neighboring elements.
+    Lower,
+    /// Use the higher of the two neighboring elements.
+    Higher,
+    /// Use whichever neighboring element is closer; a tie goes to the one
+    /// with an even index.
+    Nearest,
+    /// Use the midpoint of the two neighboring elements.
+    Midpoint,
+}
+
+// All of these functions return None for an empty slice, and whenever the
+// answer can't be represented as a RocDec.
+//
+// Results are exact, except where the true answer has more than
+// DECIMAL_PLACES decimal places. Those get truncated toward zero, the same
+// way RocDec's own division does.
+
+/// The smallest value, or None if the slice is empty.
+pub fn min(values: &[RocDec]) -> Option<RocDec> {
+    values.iter().copied().min()
+}
+
+/// The largest value, or None if the slice is empty.
+pub fn max(values: &[RocDec]) -> Option<RocDec> {
+    values.iter().copied().max()
+}
+
+/// The arithmetic mean.
+///
+/// The sum is accumulated in a RocDecAccumulator, so this works even when
+/// the sum of the values would not fit in a RocDec.
+pub fn mean(values: &[RocDec]) -> Option<RocDec> {
+    let mut sum = RocDecAccumulator::new();
+
+    for value in values.iter() {
+        sum.add(*value);
+    }
+
+    sum.checked_div(count(values.len())?)
+}
+
+/// The mean of `values`, where each value counts as much as its
+/// corresponding entry in `weights`.
+///
+/// Returns None if the two slices have different lengths, or if the weights
+/// add up to zero.
+pub fn weighted_mean(values: &[RocDec], weights: &[RocDec]) -> Option<RocDec> {
+    if values.is_empty() || values.len() != weights.len() {
+        return None;
+    }
+
+    let mut products = RocDecAccumulator::new();
+    let mut total_weight = RocDecAccumulator::new();
+
+    for (value, weight) in values.iter().zip(weights.iter()) {
+        products.add_product(*value, *weight);
+        total_weight.add(*weight);
+    }
+
+    products.checked_div(total_weight.finish()?)
+}
+
+/// The variance of `values`, treating them as the entire population.
+pub fn population_variance(values: &[RocDec]) -> Option<RocDec> {
+    variance(values, 0)
+}
+
+/// The variance of `values`, treating them as a sample of a larger
+/// population. (That is, this applies Bessel's correction.)
+pub fn sample_variance(values: &[RocDec]) -> Option<RocDec> {
+    variance(values, 1)
+}
+
+/// The standard deviation of `values`, treating them as the entire population.
+pub fn population_stddev(values: &[RocDec]) -> Option<RocDec> {
+    stddev(values, 0)
+}
+
+/// The standard deviation of `values`, treating them as a sample of a larger
+/// population. (That is, this applies Bessel's correction.)
+pub fn sample_stddev(values: &[RocDec]) -> Option<RocDec> {
+    stddev(values, 1)
+}
+
+/// The middle value once sorted. If there are an even number of values, this
+/// is the midpoint of the two middle ones.
+pub fn median(values: &[RocDec]) -> Option<RocDec> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let sorted = sorted(values);
+    let middle = sorted.len() / 2;
+
+    if sorted.len() % 2 == 1 {
+        Some(sorted[middle])
+    } else {
+        Some(interpolate(
+            sorted[middle - 1],
+            sorted[middle],
+            RocDec::ONE_POINT_ZERO as u128 / 2,
+        ))
+    }
+}
+
+/// The value below which `percent` percent of the values fall, where
+/// `percent` is between 0.0 and 100.0 inclusive.
+///
+/// Returns None if `percent` is out of that range.
+pub fn percentile(values: &[RocDec], percent: RocDec, method: Interpolation) -> Option<RocDec> {
+    const ONE: u128 = RocDec::ONE_POINT_ZERO as u128;
+
+    if values.is_empty() || percent.0 < 0 || percent.0 > 100 * RocDec::ONE_POINT_ZERO {
+        return None;
+    }
+
+    let sorted = sorted(values);
+
+    // The (fractional) index we're looking for is percent / 100 * (len - 1).
+    // This fits comfortably in a u128, since percent is at most 100 * 10^18.
+    let rank = div_u256_by_u128(mul_u128(percent.0 as u128, sorted.len() as u128 - 1), 100).lo;
+    let index = (rank / ONE) as usize;
+    let fraction = rank % ONE;
+
+    let lower = sorted[index];
+
+    // If the rank landed exactly on an element, every method agrees.
+    if fraction == 0 {
+        return Some(lower);
+    }
+
+    let higher = sorted[index + 1];
+
+    let answer = match method {
+        Interpolation::Linear => interpolate(lower, higher, fraction),
+        Interpolation::Lower => lower,
+        Interpolation::Higher => higher,
+        Interpolation::Nearest => {
+            if fraction > ONE / 2 || (fraction == ONE / 2 && index % 2 == 1) {
+                higher
+            } else {
+                lower
+            }
+        }
+        Interpolation::Midpoint => interpolate(lower, higher, ONE / 2),
+    };
+
+    Some(answer)
+}
+
+/// Sum the values' underlying i128s as a two's complement U256.
+///
+/// This can't overflow in practice, since it would take 2^128 values.
+fn wide_sum(values: &[RocDec]) -> U256 {
+    values.iter().fold(U256::ZERO, |sum, value| {
+        sum.wrapping_add(U256::from_i128(value.0))
+    })
+}
+
+/// The sum of the squared deviations from the mean, divided by
+/// (len - degrees_of_freedom). The answer has 2 * DECIMAL_PLACES decimal places.
+fn squared_deviations(values: &[RocDec], degrees_of_freedom: u128) -> Option<U256> {
+    let len = values.len() as u128;
+
+    if len <= degrees_of_freedom {
+        return None;
+    }
+
+    // The mean got truncated, so it's off from the true mean by some
+    // remainder / len. Rather than lose that, we measure deviations from the
+    // truncated mean and correct for the difference at the end.
+    let truncated_mean = mean(values)?;
+    let remainder = wide_sum(values)
+        .wrapping_sub(mul_i128(len as i128, truncated_mean.0))
+        .checked_to_i128()?
+        .unsigned_abs();
+
+    let mut sum = U256::ZERO;
+
+    for value in values.iter() {
+        // This can't overflow, because both numbers are i128s.
+        let deviation = if value.0 >= truncated_mean.0 {
+            (value.0 as u128).wrapping_sub(truncated_mean.0 as u128)
+        } else {
+            (truncated_mean.0 as u128).wrapping_sub(value.0 as u128)
+        };
+
+        let (new_sum, overflowed) = sum.overflowing_add(mul_u128(deviation, deviation));
+
+        if overflowed {
+            return None;
+        }
+
+        sum = new_sum;
+    }
+
+    // Deviations from the truncated mean overstate the true sum of squares
+    // by exactly remainder^2 / len. Subtracting the ceiling of that and then
+    // flooring the division is the same as flooring the exact answer.
+    //
+    // remainder is less than len, so remainder^2 can't overflow a u128.
+    let squared = remainder * remainder;
+    let correction = squared / len + (squared % len != 0) as u128;
+    let sum = sum.wrapping_sub(U256 {
+        hi: 0,
+        lo: correction,
+    });
+
+    Some(div_u256_by_u128(sum, len - degrees_of_freedom))
+}
+
+fn variance(values: &[RocDec], degrees_of_freedom: u128) -> Option<RocDec> {
+    let squared = squared_deviations(values, degrees_of_freedom)?;
+
+    // Variance is never negative, so this is just a range check.
+    div_u256_by_u128(squared, RocDec::ONE_POINT_ZERO as u128)
+        .checked_to_i128()
+        .map(RocDec)
+}
+
+fn stddev(values: &[RocDec], degrees_of_freedom: u128) -> Option<RocDec> {
+    // The square root of a number with 2 * DECIMAL_PLACES decimal places
+    // has exactly DECIMAL_PLACES decimal places, so no rescaling is needed.
+    let root = isqrt(squared_deviations(values, degrees_of_freedom)?);
+
+    U256 { hi: 0, lo: root }.checked_to_i128().map(RocDec)
+}
+
+/// The floor of the square root of a U256, found one bit at a time.
+fn isqrt(num: U256) -> u128 {
+    let mut root = 0u128;
+
+    for bit in (0..128).rev() {
+        let candidate = root | (1 << bit);
+
+        if mul_u128(candidate, candidate) <= num {
+            root = candidate;
+        }
+    }
+
+    root
+}
+
+/// lower + (higher - lower) * fraction, where fraction has DECIMAL_PLACES
+/// decimal places and is between 0 and 1. The answer is truncated toward zero.
+fn interpolate(lower: RocDec, higher: RocDec, fraction: u128) -> RocDec {
+    const ONE: u128 = RocDec::ONE_POINT_ZERO as u128;
+
+    // The values are sorted, so this difference is nonnegative, and it
+    // always fits in a u128 even when it wouldn't fit in an i128.
+    let difference = (higher.0 as u128).wrapping_sub(lower.0 as u128);
+    let product = mul_u128(difference, fraction);
+    let step = div_u256_by_u128(product, ONE).lo;
+    let is_exact = mul_u128(step, ONE) == product;
+
+    // The step is between 0 and the difference, so the answer is between
+    // lower and higher and this addition can't really overflow.
+    let answer = lower.0.wrapping_add(step as i128);
+
+    // Adding a floored step gives a floored answer. For negative answers,
+    // truncating toward zero means rounding that back up.
+    if !is_exact && answer.is_negative() {
+        RocDec(answer + 1)
+    } else {
+        RocDec(answer)
+    }
+}
+
+/// A slice's length as a RocDec, or None if it's empty.
+fn count(len: usize) -> Option<RocDec> {
+    if len == 0 {
+        None
+    } else {
+        // usize is at most 64 bits, so this can't truncate.
+        Some(RocDec::from(len as u64))
+    }
+}
+
+fn sorted(values: &[RocDec]) -> Vec<RocDec> {
+    let mut sorted = values.to_vec();
+
+    sorted.sort_unstable();
+
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interpolation;
+    use crate::{stats, RocDec};
+    use std::convert::TryInto;
+
+    fn decs(strings: &[&str]) -> Vec<RocDec> {
+        strings.iter().map(|s| (*s).try_into().unwrap()).collect()
+    }
+
+    fn assert_stat(expected: &str, answer: Option<RocDec>) {
+        assert_eq!(
+            Some(expected.to_string()),
+            answer.map(|dec| dec.to_string())
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(None, stats::min(&[]));
+        assert_eq!(None, stats::max(&[]));
+        assert_eq!(None, stats::mean(&[]));
+        assert_eq!(None, stats::median(&[]));
+        assert_eq!(None, stats::population_variance(&[]));
+        assert_eq!(None, stats::sample_variance(&decs(&["1.0"])));
+        assert_eq!(
+            None,
+            stats::percentile(&[], RocDec(0), Interpolation::Linear)
+        );
+    }
+
+    #[test]
+    fn min_max() {
+        let values = decs(&["3.5", "-2.25", "10.0", "0.0"]);
+
+        assert_stat("-2.25", stats::min(&values));
+        assert_stat("10.0", stats::max(&values));
+    }
+
+    #[test]
+    fn mean() {
+        assert_stat("2.0", stats::mean(&decs(&["1.0", "2.0", "3.0"])));
+        assert_stat("0.2", stats::mean(&decs(&["0.1", "0.2", "0.3"])));
+        assert_stat(
+            "0.333333333333333333",
+            stats::mean(&decs(&["1.0", "0.0", "0.0"])),
+        );
+        assert_stat(
+            "-0.333333333333333333",
+            stats::mean(&decs(&["-1.0", "0.0", "0.0"])),
+        );
+    }
+
+    #[test]
+    fn mean_does_not_overflow() {
+        // The sum of these overflows a RocDec, but the mean doesn't.
+        assert_stat(
+            super::super::MAX_STR,
+            stats::mean(&[RocDec::MAX, RocDec::MAX, RocDec::MAX]),
+        );
+        assert_stat(
+            super::super::MIN_STR,
+            stats::mean(&[RocDec::MIN, RocDec::MIN]),
+        );
+        assert_stat("0.0", stats::mean(&[RocDec::MAX, RocDec::MIN]));
+    }
+
+    #[test]
+    fn weighted_mean() {
+        assert_stat(
+            "2.25",
+            stats::weighted_mean(&decs(&["1.0", "2.0", "3.0"]), &decs(&["1.0", "1.0", "2.0"])),
+        );
+        assert_stat(
+            "10.25",
+            stats::weighted_mean(&decs(&["10.0", "11.0"]), &decs(&["0.75", "0.25"])),
+        );
+        assert_eq!(
+            None,
+            stats::weighted_mean(&decs(&["1.0", "2.0"]), &decs(&["1.0", "-1.0"]))
+        );
+        assert_eq!(
+            None,
+            stats::weighted_mean(&decs(&["1.0", "2.0"]), &decs(&["1.0"]))
+        );
+    }
+
+    #[test]
+    fn variance() {
+        let values = decs(&["2.0", "4.0", "4.0", "4.0", "5.0", "5.0", "7.0", "9.0"]);
+
+        assert_stat("4.0", stats::population_variance(&values));
+        assert_stat("4.571428571428571428", stats::sample_variance(&values));
+        assert_stat("2.0", stats::population_stddev(&values));
+        assert_stat("2.138089935299395077", stats::sample_stddev(&values));
+    }
+
+    #[test]
+    fn variance_with_truncated_mean() {
+        // The mean of these is 1/3, which can't be represented exactly.
+        let values = decs(&["1.0", "0.0", "0.0"]);
+
+        assert_stat("0.222222222222222222", stats::population_variance(&values));
+        assert_stat("0.333333333333333333", stats::sample_variance(&values));
+        assert_stat("0.471404520791031682", stats::population_stddev(&values));
+        assert_stat("0.577350269189625764", stats::sample_stddev(&values));
+    }
+
+    #[test]
+    fn variance_extremes() {
+        assert_stat(
+            "0.0",
+            stats::population_variance(&[RocDec::MAX, RocDec::MAX]),
+        );
+        assert_eq!(
+            None,
+            stats::population_variance(&[RocDec::MAX, RocDec::MIN])
+        );
+        assert_stat(
+            super::super::MAX_STR,
+            stats::population_stddev(&[RocDec::MAX, RocDec::MIN]),
+        );
+    }
+
+    #[test]
+    fn median() {
+        assert_stat("3.0", stats::median(&decs(&["5.0", "3.0", "1.0"])));
+        assert_stat("2.5", stats::median(&decs(&["4.0", "1.0", "3.0", "2.0"])));
+        assert_stat(
+            "-0.000000000000000001",
+            stats::median(&[RocDec(-3), RocDec(0)]),
+        );
+        assert_stat("0.0", stats::median(&[RocDec::MAX, RocDec::MIN]));
+    }
+
+    #[test]
+    fn percentile() {
+        let values = decs(&["1.0", "2.0", "3.0", "4.0"]);
+        let p =
+            |percent: &str, method| stats::percentile(&values, percent.try_into().unwrap(), method);
+
+        assert_stat("1.0", p("0.0", Interpolation::Linear));
+        assert_stat("4.0", p("100.0", Interpolation::Linear));
+        assert_stat("1.75", p("25.0", Interpolation::Linear));
+        assert_stat("1.0", p("25.0", Interpolation::Lower));
+        assert_stat("2.0", p("25.0", Interpolation::Higher));
+        assert_stat("2.0", p("25.0", Interpolation::Nearest));
+        assert_stat("1.5", p("25.0", Interpolation::Midpoint));
+        assert_stat("3.7", p("90.0", Interpolation::Linear));
+        assert_stat("2.5", p("50.0", Interpolation::Linear));
+        assert_stat("3.0", p("50.0", Interpolation::Nearest));
+        assert_stat("2.0", p("50.0", Interpolation::Lower));
+        assert_eq!(None, p("100.000000000000000001", Interpolation::Linear));
+        assert_eq!(None, p("-1.0", Interpolation::Linear));
+    }
+
+    #[test]
+    fn percentile_nearest_tie_goes_to_even() {
+        let values = decs(&["10.0", "20.0", "30.0"]);
+        let p = |percent: &str| {
+            stats::percentile(&values, percent.try_into().unwrap(), Interpolation::Nearest)
+        };
+
+        // Rank 0.5 is between indices 0 and 1, and rank 1.5 is between 1 and 2.
+        assert_stat("10.0", p("25.0"));
+        assert_stat("30.0", p("75.0"));
+    }
+}
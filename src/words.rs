@@ -0,0 +1,468 @@
+use crate::RocDec;
+
+/// How to write the part of the amount after the decimal point.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FractionStyle {
+    /// "and 56/100", the way cheques are written.
+    Fraction,
+    /// "and fifty-six hundredths", or "and fifty-six cents" with a currency.
+    /// Nothing at all if there's no fractional part.
+    Words,
+    /// "point five six", digit by digit.
+    Decimal,
+}
+
+/// Options for writing a RocDec out in English words, e.g. for the amount
+/// line of a cheque.
+///
+/// The defaults give e.g. "one thousand two hundred thirty-four and 56/100".
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WordsOptions<'a> {
+    currency: Option<CurrencyNames<'a>>,
+    fraction_style: FractionStyle,
+    places: u32,
+    capitalize: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+struct CurrencyNames<'a> {
+    major: &'a str,
+    major_plural: &'a str,
+    minor: &'a str,
+    minor_plural: &'a str,
+}
+
+impl<'a> Default for WordsOptions<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> WordsOptions<'a> {
+    pub fn new() -> Self {
+        WordsOptions {
+            currency: None,
+            fraction_style: FractionStyle::Fraction,
+            places: 2,
+            capitalize: false,
+        }
+    }
+
+    /// Name the currency, e.g. `currency("dollar", "dollars", "cent", "cents")`.
+    ///
+    /// With FractionStyle::Fraction, this gives "... and 56/100 dollars".
+    /// With FractionStyle::Words, it gives "... dollars and fifty-six cents",
+    /// as long as the amount has no more than `places` decimal places.
+    pub fn currency(
+        mut self,
+        major: &'a str,
+        major_plural: &'a str,
+        minor: &'a str,
+        minor_plural: &'a str,
+    ) -> Self {
+        self.currency = Some(CurrencyNames {
+            major,
+            major_plural,
+            minor,
+            minor_plural,
+        });
+
+        self
+    }
+
+    pub fn fraction_style(mut self, style: FractionStyle) -> Self {
+        self.fraction_style = style;
+
+        self
+    }
+
+    /// Write at least this many decimal places, e.g. "and 50/100" rather
+    /// than "and 5/10" for 2. If the amount has more decimal places than
+    /// this, they're all written, so the words always match it exactly.
+    ///
+    /// The default is 2, and anything over DECIMAL_PLACES is treated as
+    /// DECIMAL_PLACES.
+    pub fn places(mut self, places: u32) -> Self {
+        self.places = places;
+
+        self
+    }
+
+    /// Start with a capital letter, e.g. "One thousand...".
+    pub fn capitalize(mut self, capitalize: bool) -> Self {
+        self.capitalize = capitalize;
+
+        self
+    }
+}
+
+impl RocDec {
+    /// The amount in English words with the default options, e.g.
+    /// "one thousand two hundred thirty-four and 56/100".
+    pub fn to_words(&self) -> String {
+        self.to_words_with(&WordsOptions::new())
+    }
+
+    /// The amount in English words, using the given options.
+    pub fn to_words_with(&self, options: &WordsOptions<'_>) -> String {
+        let mut buf = [0; Self::MAX_STR_LEN];
+        let string = self.to_str_buf(&mut buf);
+        let (is_negative, unsigned) = match string.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, string),
+        };
+
+        // to_str_buf always writes at least one digit after the point, but
+        // that's only significant if it isn't the "0" in e.g. "12.0".
+        let point = unsigned.find('.').unwrap_or(unsigned.len());
+        let int_digits = &unsigned[..point];
+        let frac_digits = unsigned[point + 1..].trim_end_matches('0');
+
+        let int_part: u128 = int_digits.parse().unwrap_or(0);
+        let min_places = options.places.min(Self::DECIMAL_PLACES) as usize;
+        let places = frac_digits.len().max(min_places);
+
+        // The digits after the point, padded with zeroes to `places`.
+        let mut numerator = frac_digits.to_string();
+
+        numerator.extend(std::iter::repeat('0').take(places - frac_digits.len()));
+
+        let mut words = String::new();
+
+        if is_negative {
+            words.push_str("minus ");
+        }
+
+        push_number(&mut words, int_part);
+
+        match options.fraction_style {
+            FractionStyle::Fraction => {
+                if places > 0 {
+                    words.push_str(" and ");
+                    words.push_str(&numerator);
+                    words.push_str("/1");
+                    words.extend(std::iter::repeat('0').take(places));
+                }
+
+                if let Some(currency) = &options.currency {
+                    words.push(' ');
+                    words.push_str(currency.major_plural);
+                }
+            }
+            FractionStyle::Words => {
+                let frac_part: u128 = numerator.parse().unwrap_or(0);
+
+                if let Some(currency) = &options.currency {
+                    words.push(' ');
+                    words.push_str(if int_part == 1 {
+                        currency.major
+                    } else {
+                        currency.major_plural
+                    });
+                }
+
+                if frac_part != 0 {
+                    words.push_str(" and ");
+                    push_number(&mut words, frac_part);
+                    words.push(' ');
+
+                    match &options.currency {
+                        Some(currency) if places == min_places => {
+                            words.push_str(if frac_part == 1 {
+                                currency.minor
+                            } else {
+                                currency.minor_plural
+                            });
+                        }
+                        _ => push_denominator(&mut words, places, frac_part != 1),
+                    }
+                }
+            }
+            FractionStyle::Decimal => {
+                if places > 0 {
+                    words.push_str(" point");
+
+                    for digit in numerator.bytes() {
+                        words.push(' ');
+                        words.push_str(ONES[(digit - b'0') as usize]);
+                    }
+                }
+
+                if let Some(currency) = &options.currency {
+                    words.push(' ');
+                    words.push_str(currency.major_plural);
+                }
+            }
+        }
+
+        if options.capitalize {
+            if let Some(first) = words.get_mut(..1) {
+                first.make_ascii_uppercase();
+            }
+        }
+
+        words
+    }
+}
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// The names of 1000^1, 1000^2, etc. A RocDec's integer part is less than
+/// 1000^7, so these are all we need.
+const SCALES: [&str; 7] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+/// Append a whole number in words, e.g. "one thousand two hundred
+/// thirty-four".
+fn push_number(words: &mut String, num: u128) {
+    if num == 0 {
+        words.push_str(ONES[0]);
+        return;
+    }
+
+    // Split it into groups of three digits, most significant first.
+    let mut groups = [0u16; 13];
+    let mut count = 0;
+    let mut rest = num;
+
+    while rest != 0 {
+        groups[count] = (rest % 1000) as u16;
+        rest /= 1000;
+        count += 1;
+    }
+
+    let mut is_first = true;
+
+    for scale in (0..count).rev() {
+        let group = groups[scale];
+
+        if group == 0 {
+            continue;
+        }
+
+        if !is_first {
+            words.push(' ');
+        }
+
+        push_hundreds(words, group);
+
+        if scale > 0 {
+            words.push(' ');
+            words.push_str(SCALES[scale]);
+        }
+
+        is_first = false;
+    }
+}
+
+/// Append a number from 1 to 999 in words.
+fn push_hundreds(words: &mut String, num: u16) {
+    let hundreds = num / 100;
+    let rest = (num % 100) as usize;
+
+    if hundreds > 0 {
+        words.push_str(ONES[hundreds as usize]);
+        words.push_str(" hundred");
+
+        if rest > 0 {
+            words.push(' ');
+        }
+    }
+
+    if rest >= 20 {
+        words.push_str(TENS[rest / 10]);
+
+        if rest % 10 != 0 {
+            words.push('-');
+            words.push_str(ONES[rest % 10]);
+        }
+    } else if rest > 0 {
+        words.push_str(ONES[rest]);
+    }
+}
+
+/// Append the name of 1/10^places as a denominator, e.g. "hundredths" or
+/// "ten-thousandths".
+fn push_denominator(words: &mut String, places: usize, is_plural: bool) {
+    match places {
+        1 => words.push_str("tenth"),
+        2 => words.push_str("hundredth"),
+        _ => {
+            match places % 3 {
+                1 => words.push_str("ten-"),
+                2 => words.push_str("hundred-"),
+                _ => {}
+            }
+
+            words.push_str(SCALES[places / 3]);
+            words.push_str("th");
+        }
+    }
+
+    if is_plural {
+        words.push('s');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FractionStyle, WordsOptions};
+    use crate::RocDec;
+    use std::convert::TryInto;
+
+    fn dec(string: &str) -> RocDec {
+        string.try_into().unwrap()
+    }
+
+    fn assert_words(string: &str, options: &WordsOptions<'_>, expected: &str) {
+        assert_eq!(expected, dec(string).to_words_with(options), "{:?}", string);
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(
+            "one thousand two hundred thirty-four and 56/100",
+            dec("1234.56").to_words()
+        );
+        assert_eq!("zero and 00/100", dec("0.0").to_words());
+        assert_eq!("seven and 50/100", dec("7.5").to_words());
+        assert_eq!("minus twelve and 05/100", dec("-12.05").to_words());
+    }
+
+    #[test]
+    fn whole_numbers() {
+        let options = WordsOptions::new().places(0);
+
+        assert_words("0.0", &options, "zero");
+        assert_words("13.0", &options, "thirteen");
+        assert_words("40.0", &options, "forty");
+        assert_words("99.0", &options, "ninety-nine");
+        assert_words("100.0", &options, "one hundred");
+        assert_words("101.0", &options, "one hundred one");
+        assert_words("1000000.0", &options, "one million");
+        assert_words("1001000.0", &options, "one million one thousand");
+        assert_words("2000000015.0", &options, "two billion fifteen");
+        assert_words(
+            "170141183460469231731.0",
+            &options,
+            "one hundred seventy quintillion one hundred forty-one quadrillion \
+             one hundred eighty-three trillion four hundred sixty billion \
+             four hundred sixty-nine million two hundred thirty-one thousand \
+             seven hundred thirty-one",
+        );
+    }
+
+    #[test]
+    fn exact_fractions() {
+        // More decimal places than `places` are written out in full, rather
+        // than rounded away.
+        assert_words("1.005", &WordsOptions::new(), "one and 005/1000");
+        assert_words(
+            "0.000000000000000001",
+            &WordsOptions::new(),
+            "zero and 000000000000000001/1000000000000000000",
+        );
+        assert_words("2.5", &WordsOptions::new().places(0), "two and 5/10");
+    }
+
+    #[test]
+    fn cheque() {
+        let options = WordsOptions::new()
+            .currency("dollar", "dollars", "cent", "cents")
+            .capitalize(true);
+
+        assert_words(
+            "1234.56",
+            &options,
+            "One thousand two hundred thirty-four and 56/100 dollars",
+        );
+        assert_words("1.0", &options, "One and 00/100 dollars");
+    }
+
+    #[test]
+    fn words_style() {
+        let options = WordsOptions::new().fraction_style(FractionStyle::Words);
+
+        assert_words(
+            "1234.56",
+            &options,
+            "one thousand two hundred thirty-four and fifty-six hundredths",
+        );
+        assert_words("3.0", &options, "three");
+        assert_words("0.1", &options, "zero and ten hundredths");
+        assert_words("0.1", &options.places(1), "zero and one tenth");
+        assert_words(
+            "2.125",
+            &options,
+            "two and one hundred twenty-five thousandths",
+        );
+        assert_words("0.0007", &options, "zero and seven ten-thousandths");
+        assert_words("0.000001", &options, "zero and one millionth");
+        assert_words(
+            "0.000000000000000003",
+            &options,
+            "zero and three quintillionths",
+        );
+    }
+
+    #[test]
+    fn words_style_currency() {
+        let options = WordsOptions::new()
+            .fraction_style(FractionStyle::Words)
+            .currency("euro", "euros", "cent", "cents");
+
+        assert_words("1.01", &options, "one euro and one cent");
+        assert_words("12.5", &options, "twelve euros and fifty cents");
+        assert_words("12.0", &options, "twelve euros");
+        assert_words("0.99", &options, "zero euros and ninety-nine cents");
+
+        // Fractions of a cent can't be written in cents.
+        assert_words("1.005", &options, "one euro and five thousandths");
+    }
+
+    #[test]
+    fn decimal_style() {
+        let options = WordsOptions::new().fraction_style(FractionStyle::Decimal);
+
+        assert_words("3.14159", &options, "three point one four one five nine");
+        assert_words("3.5", &options, "three point five zero");
+        assert_words("3.0", &options.places(0), "three");
+        assert_words(
+            "-0.25",
+            &options.currency("pound", "pounds", "penny", "pence"),
+            "minus zero point two five pounds",
+        );
+    }
+}